@@ -0,0 +1,422 @@
+
+use std::borrow::Borrow;
+use std::cmp;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::Hasher;
+use std::ops::{Deref, RangeBounds};
+use std::str;
+
+use crate::ArrayString;
+use crate::arrayvec::resolve_range;
+use crate::errors::AsciiError;
+
+/// A string with a fixed capacity that only contains ASCII characters.
+///
+/// The `ArrayAsciiString` is an [`ArrayString`] in which every byte is less
+/// than `0x80`. Since every byte is a whole character, characters can be read
+/// and written by byte index in constant time, and every byte index is a
+/// `char` boundary, so slicing does not panic on boundaries.
+///
+/// The string dereferences to `str`, and converting it into an `ArrayString`
+/// is free.
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArrayAsciiString<const CAP: usize> {
+    // every byte is ASCII
+    string: ArrayString<CAP>,
+}
+
+impl<const CAP: usize> ArrayAsciiString<CAP>
+{
+    /// Create a new empty `ArrayAsciiString`.
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let mut string = ArrayAsciiString::<16>::new();
+    /// string.push_str("foo");
+    /// assert_eq!(&string[..], "foo");
+    /// assert_eq!(string.capacity(), 16);
+    /// ```
+    pub fn new() -> ArrayAsciiString<CAP> {
+        ArrayAsciiString { string: ArrayString::new() }
+    }
+
+    /// Create a new empty `ArrayAsciiString` (const fn).
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// static STRING: ArrayAsciiString<16> = ArrayAsciiString::new_const();
+    /// ```
+    pub const fn new_const() -> ArrayAsciiString<CAP> {
+        ArrayAsciiString { string: ArrayString::new_const() }
+    }
+
+    /// Create a new `ArrayAsciiString` from a `str`.
+    ///
+    /// **Errors** if the string is not ASCII, or if it does not fit in the
+    /// capacity.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayAsciiString, AsciiError};
+    ///
+    /// let string = ArrayAsciiString::<8>::from("AAPL").unwrap();
+    /// assert_eq!(&string[..], "AAPL");
+    ///
+    /// assert_eq!(ArrayAsciiString::<8>::from("Grüße"), Err(AsciiError::NonAscii(2)));
+    /// assert!(matches!(ArrayAsciiString::<2>::from("AAPL"), Err(AsciiError::Capacity(_))));
+    /// ```
+    pub fn from(s: &str) -> Result<Self, AsciiError> {
+        let mut string = Self::new();
+        string.try_push_str(s)?;
+        Ok(string)
+    }
+
+    /// Create a new `ArrayAsciiString` from a slice of bytes.
+    ///
+    /// **Errors** if the bytes are not ASCII, or if they do not fit in the
+    /// capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let string = ArrayAsciiString::<8>::from_bytes(b"MSFT").unwrap();
+    /// assert_eq!(&string[..], "MSFT");
+    /// assert!(ArrayAsciiString::<8>::from_bytes(b"\xff").is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AsciiError> {
+        check_ascii(bytes, 0)?;
+        // SAFETY: ASCII is valid UTF-8
+        Self::from(unsafe { str::from_utf8_unchecked(bytes) })
+    }
+
+    /// Return the length of the string.
+    #[inline]
+    pub const fn len(&self) -> usize { self.string.len() }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `ArrayAsciiString`.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Return true if the `ArrayAsciiString` is completely filled.
+    pub const fn is_full(&self) -> bool { self.string.is_full() }
+
+    /// Returns the capacity left in the `ArrayAsciiString`.
+    pub const fn remaining_capacity(&self) -> usize {
+        self.string.remaining_capacity()
+    }
+
+    /// Adds the given ASCII char to the end of the string.
+    ///
+    /// ***Panics*** if the char is not ASCII or if the backing array is full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let mut string = ArrayAsciiString::<2>::new();
+    /// string.push('a');
+    /// string.push('b');
+    /// assert_eq!(&string[..], "ab");
+    /// ```
+    #[track_caller]
+    pub fn push(&mut self, c: char) {
+        self.try_push(c).unwrap();
+    }
+
+    /// Adds the given ASCII char to the end of the string.
+    ///
+    /// **Errors** if the char is not ASCII or if the backing array is full.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayAsciiString, AsciiError};
+    ///
+    /// let mut string = ArrayAsciiString::<1>::new();
+    /// assert_eq!(string.try_push('é'), Err(AsciiError::NonAscii(0)));
+    /// string.try_push('a').unwrap();
+    /// assert!(matches!(string.try_push('b'), Err(AsciiError::Capacity(_))));
+    /// ```
+    pub fn try_push(&mut self, c: char) -> Result<(), AsciiError> {
+        if !c.is_ascii() {
+            return Err(AsciiError::NonAscii(self.len()));
+        }
+        self.string.try_push(c).map_err(|e| AsciiError::Capacity(e.simplify()))
+    }
+
+    /// Adds the given ASCII string slice to the end of the string.
+    ///
+    /// ***Panics*** if the string is not ASCII or if the backing array is
+    /// full.
+    #[track_caller]
+    pub fn push_str(&mut self, s: &str) {
+        self.try_push_str(s).unwrap();
+    }
+
+    /// Adds the given ASCII string slice to the end of the string.
+    ///
+    /// **Errors** if the string is not ASCII or if it does not fit in the
+    /// remaining capacity. The string is left unchanged on error.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let mut string = ArrayAsciiString::<8>::new();
+    /// string.try_push_str("Content").unwrap();
+    /// assert!(string.try_push_str("-Type").is_err());
+    /// assert_eq!(&string[..], "Content");
+    /// ```
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), AsciiError> {
+        check_ascii(s.as_bytes(), self.len())?;
+        self.string.try_push_str(s).map_err(|e| AsciiError::Capacity(e.simplify()))
+    }
+
+    /// Removes the last character from the string and returns it.
+    ///
+    /// Returns `None` if this `ArrayAsciiString` is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        self.string.pop()
+    }
+
+    /// Shortens this `ArrayAsciiString` to the specified length.
+    ///
+    /// If `new_len` is greater than the string’s current length, this has no
+    /// effect. Since every byte is a character, this never panics.
+    pub fn truncate(&mut self, new_len: usize) {
+        self.string.truncate(new_len)
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        self.string.clear()
+    }
+
+    /// Return the character at byte index `idx`.
+    ///
+    /// ***Panics*** if `idx` is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let string = ArrayAsciiString::<8>::from("GOOG").unwrap();
+    /// assert_eq!(string.char_at(1), 'O');
+    /// ```
+    #[inline]
+    pub fn char_at(&self, idx: usize) -> char {
+        self.as_bytes()[idx] as char
+    }
+
+    /// Replace the character at byte index `idx` with `c`.
+    ///
+    /// ***Panics*** if `idx` is out of bounds or if `c` is not ASCII.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let mut string = ArrayAsciiString::<8>::from("GOOG").unwrap();
+    /// string.set_char_at(0, 'E');
+    /// assert_eq!(&string[..], "EOOG");
+    /// ```
+    #[inline]
+    pub fn set_char_at(&mut self, idx: usize, c: char) {
+        assert!(c.is_ascii(), "ArrayAsciiString::set_char_at: char {:?} is not ASCII", c);
+        // SAFETY: writing an ASCII byte over an ASCII byte keeps the string valid
+        unsafe {
+            self.string.as_mut_vec()[idx] = c as u8;
+        }
+    }
+
+    /// Return the string slice of the bytes in `range`.
+    ///
+    /// Every byte index is a `char` boundary, so this only panics if the
+    /// range is out of bounds.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the string.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    ///
+    /// let string = ArrayAsciiString::<16>::from("X-Request-Id").unwrap();
+    /// assert_eq!(string.slice(2..9), "Request");
+    /// assert_eq!(string.slice(10..), "Id");
+    /// ```
+    pub fn slice<R>(&self, range: R) -> &str
+        where R: RangeBounds<usize>
+    {
+        let (start, end) = resolve_range(range, self.len());
+        let bytes = &self.as_bytes()[start..end];
+        // SAFETY: ASCII is valid UTF-8
+        unsafe { str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Converts this string to its ASCII lower case equivalent in-place.
+    pub fn make_ascii_lowercase(&mut self) {
+        self.string.make_ascii_lowercase()
+    }
+
+    /// Converts this string to its ASCII upper case equivalent in-place.
+    pub fn make_ascii_uppercase(&mut self) {
+        self.string.make_ascii_uppercase()
+    }
+
+    /// Compare this string with `other`, ignoring ASCII case.
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    /// use std::cmp::Ordering;
+    ///
+    /// let string = ArrayAsciiString::<16>::from("Content-Type").unwrap();
+    /// assert_eq!(string.cmp_ignore_ascii_case("content-type"), Ordering::Equal);
+    /// assert_eq!(string.cmp_ignore_ascii_case("CONTENT-LENGTH"), Ordering::Greater);
+    /// ```
+    pub fn cmp_ignore_ascii_case(&self, other: &str) -> cmp::Ordering {
+        let lhs = self.bytes().map(|b| b.to_ascii_lowercase());
+        let rhs = other.bytes().map(|b| b.to_ascii_lowercase());
+        lhs.cmp(rhs)
+    }
+
+    /// Feed this string into `state`, ignoring ASCII case.
+    ///
+    /// Strings that are equal ignoring ASCII case hash to the same value, which
+    /// is useful for implementing `Hash` consistently with
+    /// [`eq_ignore_ascii_case`](str::eq_ignore_ascii_case).
+    ///
+    /// ```
+    /// use arrayvec::ArrayAsciiString;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// fn hash(s: &ArrayAsciiString<16>) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     s.hash_ignore_ascii_case(&mut hasher);
+    ///     hasher.finish()
+    /// }
+    ///
+    /// let a = ArrayAsciiString::from("Content-Type").unwrap();
+    /// let b = ArrayAsciiString::from("content-type").unwrap();
+    /// assert_eq!(hash(&a), hash(&b));
+    /// ```
+    pub fn hash_ignore_ascii_case<H: Hasher>(&self, state: &mut H) {
+        for b in self.bytes() {
+            state.write_u8(b.to_ascii_lowercase());
+        }
+        // the same terminator as `str`, so that prefixes hash differently
+        state.write_u8(0xff);
+    }
+
+    /// Return a string slice of the whole `ArrayAsciiString`.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Return a reference to the string as an `ArrayString`.
+    pub fn as_array_string(&self) -> &ArrayString<CAP> {
+        &self.string
+    }
+
+    /// Convert into an `ArrayString`.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayAsciiString, ArrayString};
+    ///
+    /// let string = ArrayAsciiString::<8>::from("AAPL").unwrap();
+    /// let string: ArrayString<8> = string.into_array_string();
+    /// assert_eq!(&string[..], "AAPL");
+    /// ```
+    pub fn into_array_string(self) -> ArrayString<CAP> {
+        self.string
+    }
+}
+
+/// Return an error for the first non-ASCII byte, at `offset` plus its index.
+fn check_ascii(bytes: &[u8], offset: usize) -> Result<(), AsciiError> {
+    match bytes.iter().position(|b| !b.is_ascii()) {
+        Some(index) => Err(AsciiError::NonAscii(offset + index)),
+        None => Ok(()),
+    }
+}
+
+impl<const CAP: usize> Deref for ArrayAsciiString<CAP>
+{
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        &self.string
+    }
+}
+
+impl<const CAP: usize> PartialEq<str> for ArrayAsciiString<CAP>
+{
+    fn eq(&self, rhs: &str) -> bool {
+        &**self == rhs
+    }
+}
+
+impl<const CAP: usize> PartialEq<ArrayAsciiString<CAP>> for str
+{
+    fn eq(&self, rhs: &ArrayAsciiString<CAP>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl<const CAP: usize> Borrow<str> for ArrayAsciiString<CAP>
+{
+    fn borrow(&self) -> &str { self }
+}
+
+impl<const CAP: usize> AsRef<str> for ArrayAsciiString<CAP>
+{
+    fn as_ref(&self) -> &str { self }
+}
+
+impl<const CAP: usize> fmt::Debug for ArrayAsciiString<CAP>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<const CAP: usize> fmt::Display for ArrayAsciiString<CAP>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<const CAP: usize> From<ArrayAsciiString<CAP>> for ArrayString<CAP>
+{
+    fn from(s: ArrayAsciiString<CAP>) -> Self {
+        s.string
+    }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a str> for ArrayAsciiString<CAP>
+{
+    type Error = AsciiError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::from(s)
+    }
+}
+
+impl<const CAP: usize> TryFrom<ArrayString<CAP>> for ArrayAsciiString<CAP>
+{
+    type Error = AsciiError;
+
+    fn try_from(string: ArrayString<CAP>) -> Result<Self, Self::Error> {
+        check_ascii(string.as_bytes(), 0)?;
+        Ok(ArrayAsciiString { string })
+    }
+}
+
+impl<const CAP: usize> fmt::Write for ArrayAsciiString<CAP>
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
@@ -0,0 +1,306 @@
+
+use std::borrow::Borrow;
+use std::cmp;
+use std::convert::TryFrom;
+use std::ffi::CStr;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::os::raw::c_char;
+use std::str::Utf8Error;
+
+use crate::ArrayString;
+use crate::ArrayVec;
+use crate::CapacityError;
+use crate::errors::CStringError;
+use crate::utils::CapacityFits;
+
+/// A nul-terminated C string with a fixed capacity.
+///
+/// The `ArrayCString` is a string backed by an `ArrayVec<u8, CAP>`. It never
+/// contains interior nul bytes and always keeps a nul terminator, so the
+/// capacity `CAP` includes the terminator and the string itself holds at most
+/// `CAP - 1` bytes. It must be at least 1, which is checked at compile time.
+///
+/// The string dereferences to [`CStr`], and [`.as_ptr()`](ArrayCString::as_ptr)
+/// can be passed directly to C functions expecting a `*const c_char`.
+///
+/// Requires `features="std"`.
+#[derive(Clone)]
+pub struct ArrayCString<const CAP: usize> {
+    // the bytes including the nul terminator, which is always present
+    vec: ArrayVec<u8, CAP>,
+}
+
+impl<const CAP: usize> Default for ArrayCString<CAP>
+{
+    /// Return an empty `ArrayCString`
+    fn default() -> ArrayCString<CAP> {
+        ArrayCString::new()
+    }
+}
+
+impl<const CAP: usize> ArrayCString<CAP>
+{
+    /// Create a new empty `ArrayCString`.
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use arrayvec::ArrayCString;
+    ///
+    /// let string = ArrayCString::<16>::new();
+    /// assert!(string.is_empty());
+    /// assert_eq!(string.to_bytes_with_nul(), b"\0");
+    /// ```
+    pub fn new() -> ArrayCString<CAP> {
+        #[allow(clippy::let_unit_value)]
+        let () = CapacityFits::<1, CAP>::ASSERT;
+        let mut vec = ArrayVec::new();
+        vec.push(0);
+        ArrayCString { vec }
+    }
+
+    /// Create a new `ArrayCString` from bytes, which must not contain a nul
+    /// byte. The terminator is appended.
+    ///
+    /// **Errors** if the bytes contain a nul byte, or if they do not fit in
+    /// the capacity together with the terminator.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayCString, CStringError};
+    ///
+    /// let string = ArrayCString::<8>::from_bytes(b"hello").unwrap();
+    /// assert_eq!(string.to_bytes_with_nul(), b"hello\0");
+    ///
+    /// assert_eq!(ArrayCString::<8>::from_bytes(b"he\0llo"), Err(CStringError::InteriorNul(2)));
+    /// assert!(matches!(ArrayCString::<5>::from_bytes(b"hello"), Err(CStringError::Capacity(_))));
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CStringError> {
+        let mut string = Self::new();
+        string.try_push_bytes(bytes)?;
+        Ok(string)
+    }
+
+    /// Return the length of the string in bytes, not including the terminator.
+    #[inline]
+    pub fn len(&self) -> usize { self.vec.len() - 1 }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `ArrayCString`, including the terminator.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Return true if the `ArrayCString` is completely filled.
+    pub fn is_full(&self) -> bool { self.vec.is_full() }
+
+    /// Adds the given byte to the end of the string.
+    ///
+    /// ***Panics*** if the byte is nul or if the backing array is full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayCString;
+    ///
+    /// let mut string = ArrayCString::<3>::new();
+    /// string.push(b'a');
+    /// string.push(b'b');
+    /// assert_eq!(string.to_bytes(), b"ab");
+    /// ```
+    #[track_caller]
+    pub fn push(&mut self, byte: u8) {
+        self.try_push(byte).unwrap();
+    }
+
+    /// Adds the given byte to the end of the string.
+    ///
+    /// **Errors** if the byte is nul or if the backing array is full.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayCString, CStringError};
+    ///
+    /// let mut string = ArrayCString::<2>::new();
+    /// assert_eq!(string.try_push(0), Err(CStringError::InteriorNul(0)));
+    /// string.try_push(b'a').unwrap();
+    /// assert!(matches!(string.try_push(b'b'), Err(CStringError::Capacity(_))));
+    /// ```
+    pub fn try_push(&mut self, byte: u8) -> Result<(), CStringError> {
+        self.try_push_bytes(&[byte])
+    }
+
+    /// Adds the given bytes to the end of the string.
+    ///
+    /// **Errors** if the bytes contain a nul byte, or if they do not fit in
+    /// the remaining capacity. The string is left unchanged on error.
+    ///
+    /// ```
+    /// use arrayvec::ArrayCString;
+    ///
+    /// let mut string = ArrayCString::<8>::new();
+    /// string.try_push_bytes(b"abc").unwrap();
+    /// string.try_push_bytes(b"def").unwrap();
+    /// assert_eq!(string.to_bytes(), b"abcdef");
+    /// assert!(string.try_push_bytes(b"gh").is_err());
+    /// ```
+    pub fn try_push_bytes(&mut self, bytes: &[u8]) -> Result<(), CStringError> {
+        if let Some(index) = bytes.iter().position(|&b| b == 0) {
+            return Err(CStringError::InteriorNul(self.len() + index));
+        }
+        if bytes.len() > self.vec.remaining_capacity() {
+            return Err(CStringError::Capacity(CapacityError::new(())));
+        }
+        self.vec.pop();
+        self.vec.try_extend_from_slice(bytes).unwrap();
+        self.vec.push(0);
+        Ok(())
+    }
+
+    /// Shortens the string, keeping the first `len` bytes and dropping the
+    /// rest.
+    ///
+    /// If `len` is greater than the string’s current length, this has no
+    /// effect.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            self.vec.truncate(len);
+            self.vec.push(0);
+        }
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Return a `CStr` of the whole `ArrayCString`.
+    pub fn as_c_str(&self) -> &CStr {
+        self
+    }
+
+    /// Return a raw pointer to the nul-terminated string, for passing to C.
+    ///
+    /// The pointer is valid as long as the `ArrayCString` is not modified or
+    /// moved.
+    pub fn as_ptr(&self) -> *const c_char {
+        self.vec.as_ptr() as *const c_char
+    }
+
+    /// Return the contents as an `ArrayString`.
+    ///
+    /// **Errors** if the string is not valid UTF-8.
+    ///
+    /// ```
+    /// use arrayvec::ArrayCString;
+    ///
+    /// let string = ArrayCString::<8>::from_bytes(b"hello").unwrap();
+    /// assert_eq!(&string.to_array_string().unwrap()[..], "hello");
+    /// ```
+    pub fn to_array_string(&self) -> Result<ArrayString<CAP>, Utf8Error> {
+        let s = self.as_c_str().to_str()?;
+        // the string is shorter than `CAP`, so it always fits
+        Ok(ArrayString::from(s).unwrap())
+    }
+}
+
+impl<const CAP: usize> Deref for ArrayCString<CAP>
+{
+    type Target = CStr;
+    #[inline]
+    fn deref(&self) -> &CStr {
+        // SAFETY: the bytes end with the only nul byte
+        unsafe { CStr::from_bytes_with_nul_unchecked(&self.vec) }
+    }
+}
+
+impl<const CAP: usize> AsRef<CStr> for ArrayCString<CAP>
+{
+    fn as_ref(&self) -> &CStr { self }
+}
+
+impl<const CAP: usize> Borrow<CStr> for ArrayCString<CAP>
+{
+    fn borrow(&self) -> &CStr { self }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a CStr> for ArrayCString<CAP>
+{
+    type Error = CapacityError<&'a CStr>;
+
+    fn try_from(s: &'a CStr) -> Result<Self, Self::Error> {
+        let mut vec = ArrayVec::new();
+        match vec.try_extend_from_slice(s.to_bytes_with_nul()) {
+            Ok(()) => Ok(ArrayCString { vec }),
+            Err(_) => Err(CapacityError::new(s)),
+        }
+    }
+}
+
+impl<'a, const CAP: usize> TryFrom<&'a str> for ArrayCString<CAP>
+{
+    type Error = CStringError;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<const CAP: usize> TryFrom<ArrayString<CAP>> for ArrayCString<CAP>
+{
+    type Error = CStringError;
+
+    fn try_from(s: ArrayString<CAP>) -> Result<Self, Self::Error> {
+        Self::from_bytes(s.as_bytes())
+    }
+}
+
+impl<const CAP: usize> fmt::Debug for ArrayCString<CAP>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<const CAP: usize> PartialEq for ArrayCString<CAP>
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<const CAP: usize> PartialEq<CStr> for ArrayCString<CAP>
+{
+    fn eq(&self, rhs: &CStr) -> bool {
+        **self == *rhs
+    }
+}
+
+impl<const CAP: usize> PartialEq<ArrayCString<CAP>> for CStr
+{
+    fn eq(&self, rhs: &ArrayCString<CAP>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl<const CAP: usize> Eq for ArrayCString<CAP>
+{ }
+
+impl<const CAP: usize> Hash for ArrayCString<CAP>
+{
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        (**self).hash(h)
+    }
+}
+
+impl<const CAP: usize> PartialOrd for ArrayCString<CAP>
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const CAP: usize> Ord for ArrayCString<CAP>
+{
+    fn cmp(&self, rhs: &Self) -> cmp::Ordering {
+        (**self).cmp(&**rhs)
+    }
+}
@@ -0,0 +1,1069 @@
+//! A fixed capacity double-ended queue, [`ArrayDeque`], and its iterators.
+
+use std::cmp;
+use std::iter;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::mem::MaybeUninit;
+use std::ops::{Index, IndexMut, RangeBounds};
+use std::ptr;
+use std::slice;
+
+use std::hash::{Hash, Hasher};
+use std::fmt;
+
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::arrayvec::resolve_range;
+use crate::errors::CapacityError;
+use crate::utils::MakeMaybeUninit;
+
+/// A double-ended queue with a fixed capacity.
+///
+/// The `ArrayDeque` is a ring buffer backed by a fixed size array, using the same
+/// inline storage as [`ArrayVec`](crate::ArrayVec) plus the index of its first element.
+/// The `ArrayDeque<T, CAP>` is parameterized by `T` for the element type and `CAP` for
+/// the maximum capacity.
+///
+/// Pushing and popping at either end is O(1). Since the elements may wrap around the
+/// end of the backing array, the contents are exposed as a pair of slices (see
+/// [`as_slices`](ArrayDeque::as_slices)) or rearranged into one slice with
+/// [`make_contiguous`](ArrayDeque::make_contiguous).
+///
+/// A full deque can also be used as a bounded history buffer that never fails to
+/// accept new elements, see [`push_back_overwrite`](ArrayDeque::push_back_overwrite).
+///
+/// `CAP` is of type `usize` but is range limited to `u32::MAX`; attempting to create larger
+/// deques with larger capacity will panic.
+pub struct ArrayDeque<T, const CAP: usize> {
+    // the `len` elements starting at `head` (wrapping around) are initialized
+    xs: [MaybeUninit<T>; CAP],
+    head: u32,
+    len: u32,
+}
+
+impl<T, const CAP: usize> Drop for ArrayDeque<T, CAP> {
+    fn drop(&mut self) {
+        self.clear();
+
+        // MaybeUninit inhibits array's drop
+    }
+}
+
+impl<T, const CAP: usize> ArrayDeque<T, CAP> {
+    /// Create a new empty `ArrayDeque`.
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 16>::new();
+    /// deque.push_back(1);
+    /// deque.push_front(2);
+    /// assert_eq!(deque.as_slices(), (&[2][..], &[1][..]));
+    /// assert_eq!(deque.capacity(), 16);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn new() -> ArrayDeque<T, CAP> {
+        assert_capacity_limit!(CAP, u32);
+        unsafe {
+            ArrayDeque { xs: MaybeUninit::uninit().assume_init(), head: 0, len: 0 }
+        }
+    }
+
+    /// Create a new empty `ArrayDeque` (const fn).
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// static DEQUE: ArrayDeque<u8, 1024> = ArrayDeque::new_const();
+    /// ```
+    pub const fn new_const() -> ArrayDeque<T, CAP> {
+        assert_capacity_limit_const!(CAP, u32);
+        ArrayDeque { xs: MakeMaybeUninit::ARRAY, head: 0, len: 0 }
+    }
+
+    /// Return the number of elements in the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    /// deque.pop_front();
+    /// assert_eq!(deque.len(), 2);
+    /// ```
+    #[inline(always)]
+    pub const fn len(&self) -> usize { self.len as usize }
+
+    /// Returns whether the `ArrayDeque` is empty.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1]);
+    /// deque.pop_back();
+    /// assert_eq!(deque.is_empty(), true);
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let deque = ArrayDeque::from([1, 2, 3]);
+    /// assert_eq!(deque.capacity(), 3);
+    /// ```
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Return true if the `ArrayDeque` is completely filled to its capacity, false otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 1>::new();
+    /// assert!(!deque.is_full());
+    /// deque.push_back(1);
+    /// assert!(deque.is_full());
+    /// ```
+    pub const fn is_full(&self) -> bool { self.len() == self.capacity() }
+
+    /// Returns the capacity left in the `ArrayDeque`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    /// deque.pop_front();
+    /// assert_eq!(deque.remaining_capacity(), 1);
+    /// ```
+    pub const fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Push `element` to the back of the deque.
+    ///
+    /// ***Panics*** if the deque is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// deque.push_back(1);
+    /// deque.push_back(2);
+    ///
+    /// assert_eq!(deque.front(), Some(&1));
+    /// assert_eq!(deque.back(), Some(&2));
+    /// ```
+    #[track_caller]
+    pub fn push_back(&mut self, element: T) {
+        self.try_push_back(element).unwrap()
+    }
+
+    /// Push `element` to the back of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or return an error if the deque
+    /// is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// assert!(deque.try_push_back(1).is_ok());
+    /// assert!(deque.try_push_back(2).is_ok());
+    ///
+    /// let overflow = deque.try_push_back(3);
+    /// assert_eq!(overflow.unwrap_err().element(), 3);
+    /// ```
+    pub fn try_push_back(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.len() == CAP {
+            return Err(CapacityError::new(element));
+        }
+        unsafe {
+            let len = self.len();
+            ptr::write(self.ptr_at(len), element);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
+    /// Push `element` to the front of the deque.
+    ///
+    /// ***Panics*** if the deque is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// deque.push_front(1);
+    /// deque.push_front(2);
+    ///
+    /// assert_eq!(deque.front(), Some(&2));
+    /// assert_eq!(deque.back(), Some(&1));
+    /// ```
+    #[track_caller]
+    pub fn push_front(&mut self, element: T) {
+        self.try_push_front(element).unwrap()
+    }
+
+    /// Push `element` to the front of the deque.
+    ///
+    /// Return `Ok` if the push succeeds, or return an error if the deque
+    /// is already full.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 2>::new();
+    ///
+    /// assert!(deque.try_push_front(1).is_ok());
+    /// assert!(deque.try_push_front(2).is_ok());
+    ///
+    /// let overflow = deque.try_push_front(3);
+    /// assert_eq!(overflow.unwrap_err().element(), 3);
+    /// ```
+    pub fn try_push_front(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        if self.len() == CAP {
+            return Err(CapacityError::new(element));
+        }
+        let head = if self.head == 0 { CAP - 1 } else { self.head() - 1 };
+        self.head = head as u32;
+        unsafe {
+            ptr::write(self.ptr_at(0), element);
+        }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Push `element` to the back of the deque, evicting the front element
+    /// if the deque is already full.
+    ///
+    /// Return the evicted element, or `None` if there was room. This makes the
+    /// deque usable as a history buffer that keeps the `CAP` most recent elements;
+    /// iteration goes from the oldest to the newest element.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut history = ArrayDeque::<_, 3>::new();
+    ///
+    /// for i in 1..=3 {
+    ///     assert_eq!(history.push_back_overwrite(i), None);
+    /// }
+    /// assert_eq!(history.push_back_overwrite(4), Some(1));
+    /// assert_eq!(history.push_back_overwrite(5), Some(2));
+    /// assert!(history.iter().eq(&[3, 4, 5]));
+    /// ```
+    pub fn push_back_overwrite(&mut self, element: T) -> Option<T> {
+        if !self.is_full() {
+            self.push_back(element);
+            return None;
+        }
+        if CAP == 0 {
+            return Some(element);
+        }
+        // When full, the slot after the back is the slot of the front.
+        unsafe {
+            let ptr = self.ptr_at(0);
+            let evicted = ptr::read(ptr);
+            ptr::write(ptr, element);
+            self.head = self.to_physical(1) as u32;
+            Some(evicted)
+        }
+    }
+
+    /// Push `element` to the front of the deque, evicting the back element
+    /// if the deque is already full.
+    ///
+    /// Return the evicted element, or `None` if there was room.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3]);
+    ///
+    /// assert_eq!(deque.push_front_overwrite(0), Some(3));
+    /// assert!(deque.iter().eq(&[0, 1, 2]));
+    /// ```
+    pub fn push_front_overwrite(&mut self, element: T) -> Option<T> {
+        if !self.is_full() {
+            self.push_front(element);
+            return None;
+        }
+        if CAP == 0 {
+            return Some(element);
+        }
+        // When full, the slot before the front is the slot of the back.
+        unsafe {
+            let back = self.to_physical(CAP - 1);
+            let ptr = self.ptr_at(CAP - 1);
+            let evicted = ptr::read(ptr);
+            ptr::write(ptr, element);
+            self.head = back as u32;
+            Some(evicted)
+        }
+    }
+
+    /// Remove the first element in the deque and return it.
+    ///
+    /// Return `Some(` *element* `)` if the deque is non-empty, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2]);
+    ///
+    /// assert_eq!(deque.pop_front(), Some(1));
+    /// assert_eq!(deque.pop_front(), Some(2));
+    /// assert_eq!(deque.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            let element = ptr::read(self.ptr_at(0));
+            self.head = self.to_physical(1) as u32;
+            self.len -= 1;
+            Some(element)
+        }
+    }
+
+    /// Remove the last element in the deque and return it.
+    ///
+    /// Return `Some(` *element* `)` if the deque is non-empty, else `None`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2]);
+    ///
+    /// assert_eq!(deque.pop_back(), Some(2));
+    /// assert_eq!(deque.pop_back(), Some(1));
+    /// assert_eq!(deque.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        unsafe {
+            self.len -= 1;
+            let len = self.len();
+            Some(ptr::read(self.ptr_at(len)))
+        }
+    }
+
+    /// Return a reference to the first element, or `None` if the deque is empty.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Return a mutable reference to the first element, or `None` if the deque is empty.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Return a reference to the last element, or `None` if the deque is empty.
+    pub fn back(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(move |i| self.get(i))
+    }
+
+    /// Return a mutable reference to the last element, or `None` if the deque is empty.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        self.len().checked_sub(1).and_then(move |i| self.get_mut(i))
+    }
+
+    /// Return a reference to the element at `index`, counting from the front,
+    /// or `None` if the index is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 3>::new();
+    /// deque.push_back(2);
+    /// deque.push_front(1);
+    ///
+    /// assert_eq!(deque.get(1), Some(&2));
+    /// assert_eq!(deque.get(2), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index < self.len() {
+            unsafe { Some(&*(self.xs.as_ptr().add(self.to_physical(index)) as *const T)) }
+        } else {
+            None
+        }
+    }
+
+    /// Return a mutable reference to the element at `index`, counting from the front,
+    /// or `None` if the index is out of bounds.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.len() {
+            unsafe { Some(&mut *self.ptr_at(index)) }
+        } else {
+            None
+        }
+    }
+
+    /// Shortens the deque, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the deque’s current length this has no
+    /// effect.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::from([1, 2, 3, 4, 5]);
+    /// deque.truncate(3);
+    /// assert!(deque.iter().eq(&[1, 2, 3]));
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) {
+        let len = self.len();
+        if new_len >= len {
+            return;
+        }
+        unsafe {
+            let (front, back) = self.as_mut_slices();
+            let (front, back): (*mut [T], *mut [T]) = if new_len >= front.len() {
+                (&mut [][..], &mut back[new_len - front.len()..])
+            } else {
+                (&mut front[new_len..], back)
+            };
+            // panic safety: set the length before dropping elements
+            self.len = new_len as u32;
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(back);
+        }
+    }
+
+    /// Remove all elements in the deque.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+        self.head = 0;
+    }
+
+    /// Return the contents of the deque as a pair of slices, front to back.
+    ///
+    /// If the elements do not wrap around the end of the backing array, the second
+    /// slice is empty.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 4>::new();
+    /// deque.push_back(3);
+    /// deque.push_back(4);
+    /// deque.push_front(2);
+    /// deque.push_front(1);
+    ///
+    /// let (a, b) = deque.as_slices();
+    /// assert_eq!([a, b].concat(), [1, 2, 3, 4]);
+    /// ```
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let (head, first_len, second_len) = self.slice_ranges();
+        let ptr = self.xs.as_ptr() as *const T;
+        unsafe {
+            (slice::from_raw_parts(ptr.add(head), first_len),
+             slice::from_raw_parts(ptr, second_len))
+        }
+    }
+
+    /// Return the contents of the deque as a pair of mutable slices, front to back.
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (head, first_len, second_len) = self.slice_ranges();
+        let ptr = self.xs.as_mut_ptr() as *mut T;
+        unsafe {
+            (slice::from_raw_parts_mut(ptr.add(head), first_len),
+             slice::from_raw_parts_mut(ptr, second_len))
+        }
+    }
+
+    /// Rearrange the elements so that they are stored contiguously, and return
+    /// them as a single mutable slice.
+    ///
+    /// This is O(n) if the elements wrap around the end of the backing array,
+    /// and does nothing otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut deque = ArrayDeque::<_, 3>::new();
+    /// deque.push_back(2);
+    /// deque.push_back(3);
+    /// deque.push_front(1);
+    ///
+    /// assert_eq!(deque.make_contiguous(), &[1, 2, 3]);
+    /// assert_eq!(deque.as_slices(), (&[1, 2, 3][..], &[][..]));
+    /// ```
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        if self.head() + self.len() > CAP {
+            // Rotating the whole array moves the (uninitialized) gap between
+            // the back and the front to the end.
+            let head = self.head();
+            self.xs.rotate_left(head);
+            self.head = 0;
+        }
+        self.as_mut_slices().0
+    }
+
+    /// Return an iterator over the elements, front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (a, b) = self.as_slices();
+        Iter { a: a.iter(), b: b.iter() }
+    }
+
+    /// Return an iterator over mutable references to the elements, front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (a, b) = self.as_mut_slices();
+        IterMut { a: a.iter_mut(), b: b.iter_mut() }
+    }
+
+    /// Create a draining iterator that removes the specified range in the deque
+    /// and yields the removed items from front to back. The element range is
+    /// removed even if the iterator is not consumed until the end.
+    ///
+    /// Note: It is unspecified how many elements are removed from the deque,
+    /// if the `Drain` value is leaked.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the deque.
+    ///
+    /// ```
+    /// use arrayvec::ArrayDeque;
+    ///
+    /// let mut d1 = ArrayDeque::from([1, 2, 3, 4]);
+    /// let d2: ArrayDeque<_, 4> = d1.drain(1..3).collect();
+    /// assert!(d1.iter().eq(&[1, 4]));
+    /// assert!(d2.iter().eq(&[2, 3]));
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP>
+        where R: RangeBounds<usize>
+    {
+        // Memory safety
+        //
+        // Like `ArrayVec::drain`, the length of the deque is shortened to the start
+        // of the range while the `Drain` is alive, so that no moved-from elements are
+        // accessible if its destructor never runs. When finished, whichever of the
+        // parts before and after the range is shorter is moved to close the gap.
+        let len = self.len();
+        let (start, end) = resolve_range(range, len);
+        if start > end {
+            panic!("ArrayDeque::drain: start {} is greater than end {}", start, end);
+        }
+        if end > len {
+            panic!("ArrayDeque::drain: end {} is out of bounds in deque of length {}", end, len);
+        }
+
+        self.len = start as u32;
+
+        Drain {
+            index: start,
+            end,
+            tail_start: end,
+            tail_len: len - end,
+            deque: self as *mut _,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    fn head(&self) -> usize { self.head as usize }
+
+    /// Map a logical index (counted from the front) to an index into `xs`.
+    ///
+    /// The logical index must be at most `CAP`.
+    #[inline]
+    fn to_physical(&self, index: usize) -> usize {
+        let i = self.head() + index;
+        if i >= CAP { i - CAP } else { i }
+    }
+
+    /// Get pointer to where element at logical `index` would be
+    unsafe fn ptr_at(&mut self, index: usize) -> *mut T {
+        let i = self.to_physical(index);
+        self.xs.as_mut_ptr().add(i) as *mut T
+    }
+
+    /// Return the start of the first slice and the lengths of both slices
+    fn slice_ranges(&self) -> (usize, usize, usize) {
+        let head = self.head();
+        let len = self.len();
+        if head + len <= CAP {
+            (head, len, 0)
+        } else {
+            (head, CAP - head, head + len - CAP)
+        }
+    }
+
+    /// Move `count` elements from logical index `src` to logical index `dst`.
+    ///
+    /// The ranges may overlap; both must lie within the capacity.
+    unsafe fn copy_within(&mut self, src: usize, dst: usize, count: usize) {
+        if dst < src {
+            for i in 0..count {
+                ptr::copy_nonoverlapping(self.ptr_at(src + i), self.ptr_at(dst + i), 1);
+            }
+        } else if dst > src {
+            for i in (0..count).rev() {
+                ptr::copy_nonoverlapping(self.ptr_at(src + i), self.ptr_at(dst + i), 1);
+            }
+        }
+    }
+}
+
+impl<T, const CAP: usize> Index<usize> for ArrayDeque<T, CAP> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("ArrayDeque::index: index {} is out of bounds in deque of length {}", index, len)
+        })
+    }
+}
+
+impl<T, const CAP: usize> IndexMut<usize> for ArrayDeque<T, CAP> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("ArrayDeque::index_mut: index {} is out of bounds in deque of length {}", index, len)
+        })
+    }
+}
+
+/// Create an `ArrayDeque` from an array.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+///
+/// let deque = ArrayDeque::from([1, 2, 3]);
+/// assert_eq!(deque.len(), 3);
+/// assert_eq!(deque.capacity(), 3);
+/// ```
+impl<T, const CAP: usize> From<[T; CAP]> for ArrayDeque<T, CAP> {
+    #[track_caller]
+    fn from(array: [T; CAP]) -> Self {
+        let array = ManuallyDrop::new(array);
+        let mut deque = <ArrayDeque<T, CAP>>::new();
+        unsafe {
+            (&*array as *const [T; CAP] as *const [MaybeUninit<T>; CAP])
+                .copy_to_nonoverlapping(&mut deque.xs as *mut [MaybeUninit<T>; CAP], 1);
+        }
+        deque.len = CAP as u32;
+        deque
+    }
+}
+
+/// Iterate the `ArrayDeque` with references to each element.
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayDeque<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// Iterate the `ArrayDeque` with mutable references to each element.
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayDeque<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// Iterate the `ArrayDeque` with each element by value, front to back.
+///
+/// The deque is consumed by this operation.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+///
+/// for elt in ArrayDeque::from([1, 2, 3]) {
+///     // ...
+/// }
+/// ```
+impl<T, const CAP: usize> IntoIterator for ArrayDeque<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+    fn into_iter(self) -> IntoIter<T, CAP> {
+        IntoIter { deque: self }
+    }
+}
+
+/// Extend the `ArrayDeque` with an iterator, pushing to the back.
+///
+/// ***Panics*** if extending the deque exceeds its capacity.
+impl<T, const CAP: usize> Extend<T> for ArrayDeque<T, CAP> {
+    /// Extend the `ArrayDeque` with an iterator, pushing to the back.
+    ///
+    /// ***Panics*** if extending the deque exceeds its capacity.
+    #[track_caller]
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for elt in iter {
+            if self.try_push_back(elt).is_err() {
+                extend_panic();
+            }
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn extend_panic() {
+    panic!("ArrayDeque: capacity exceeded in extend/from_iter");
+}
+
+/// Create an `ArrayDeque` from an iterator.
+///
+/// ***Panics*** if the number of elements in the iterator exceeds the deque's capacity.
+impl<T, const CAP: usize> iter::FromIterator<T> for ArrayDeque<T, CAP> {
+    /// Create an `ArrayDeque` from an iterator.
+    ///
+    /// ***Panics*** if the number of elements in the iterator exceeds the deque's capacity.
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut deque = ArrayDeque::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, const CAP: usize> Clone for ArrayDeque<T, CAP>
+    where T: Clone
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const CAP: usize> Hash for ArrayDeque<T, CAP>
+    where T: Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hash like a slice, independent of where the elements wrap around
+        state.write_usize(self.len());
+        self.iter().for_each(|elt| elt.hash(state));
+    }
+}
+
+impl<T, const CAP: usize> PartialEq for ArrayDeque<T, CAP>
+    where T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other)
+    }
+}
+
+impl<T, const CAP: usize> Eq for ArrayDeque<T, CAP> where T: Eq { }
+
+impl<T, const CAP: usize> PartialOrd for ArrayDeque<T, CAP> where T: PartialOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.iter().partial_cmp(other)
+    }
+}
+
+impl<T, const CAP: usize> Ord for ArrayDeque<T, CAP> where T: Ord {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.iter().cmp(other)
+    }
+}
+
+impl<T, const CAP: usize> fmt::Debug for ArrayDeque<T, CAP> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self).finish()
+    }
+}
+
+impl<T, const CAP: usize> Default for ArrayDeque<T, CAP> {
+    /// Return an empty deque
+    fn default() -> ArrayDeque<T, CAP> {
+        ArrayDeque::new()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+/// "Best efforts" zeroing of the `ArrayDeque`'s buffer when the `zeroize` feature is enabled.
+///
+/// The length is set to 0, and the buffer is dropped and zeroized.
+/// Cannot ensure that previous moves of the `ArrayDeque` did not leave values on the stack.
+///
+/// ```
+/// use arrayvec::ArrayDeque;
+/// use zeroize::Zeroize;
+/// let mut deque = ArrayDeque::from([1, 2, 3]);
+/// deque.zeroize();
+/// assert_eq!(deque.len(), 0);
+/// ```
+impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayDeque<Z, CAP> {
+    fn zeroize(&mut self) {
+        // Zeroize all the contained elements.
+        let (a, b) = self.as_mut_slices();
+        a.iter_mut().zeroize();
+        b.iter_mut().zeroize();
+        // Drop all the elements and set the length to 0.
+        self.clear();
+        // Zeroize the backing array.
+        self.xs.zeroize();
+    }
+}
+
+/// Iterator over references to the elements of an `ArrayDeque`.
+pub struct Iter<'a, T> {
+    a: slice::Iter<'a, T>,
+    b: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.a.next().or_else(|| self.b.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.b.next_back().or_else(|| self.a.next_back())
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> { }
+
+impl<'a, T> Clone for Iter<'a, T> {
+    fn clone(&self) -> Self {
+        Iter { a: self.a.clone(), b: self.b.clone() }
+    }
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for Iter<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+/// Iterator over mutable references to the elements of an `ArrayDeque`.
+pub struct IterMut<'a, T> {
+    a: slice::IterMut<'a, T>,
+    b: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.a.next() {
+            Some(elt) => Some(elt),
+            None => self.b.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a.len() + self.b.len();
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.b.next_back() {
+            Some(elt) => Some(elt),
+            None => self.a.next_back(),
+        }
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> { }
+
+impl<'a, T: fmt::Debug> fmt::Debug for IterMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.a.as_slice())
+            .entries(self.b.as_slice())
+            .finish()
+    }
+}
+
+/// By-value iterator for `ArrayDeque`.
+pub struct IntoIter<T, const CAP: usize> {
+    deque: ArrayDeque<T, CAP>,
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.deque.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> { }
+
+impl<T, const CAP: usize> Clone for IntoIter<T, CAP>
+where T: Clone,
+{
+    fn clone(&self) -> IntoIter<T, CAP> {
+        self.deque.clone().into_iter()
+    }
+}
+
+impl<T, const CAP: usize> fmt::Debug for IntoIter<T, CAP>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(&self.deque)
+            .finish()
+    }
+}
+
+/// A draining iterator for `ArrayDeque`.
+pub struct Drain<'a, T: 'a, const CAP: usize> {
+    /// Logical index of the next element to yield from the front
+    index: usize,
+    /// Logical index one past the last element to yield
+    end: usize,
+    /// Logical index of tail to preserve
+    tail_start: usize,
+    /// Length of tail
+    tail_len: usize,
+    deque: *mut ArrayDeque<T, CAP>,
+    _marker: PhantomData<&'a mut ArrayDeque<T, CAP>>,
+}
+
+unsafe impl<'a, T: Sync, const CAP: usize> Sync for Drain<'a, T, CAP> {}
+unsafe impl<'a, T: Send, const CAP: usize> Send for Drain<'a, T, CAP> {}
+
+impl<'a, T: 'a, const CAP: usize> Iterator for Drain<'a, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        unsafe {
+            let elt = ptr::read((*self.deque).ptr_at(self.index));
+            self.index += 1;
+            Some(elt)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> DoubleEndedIterator for Drain<'a, T, CAP>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        unsafe {
+            self.end -= 1;
+            Some(ptr::read((*self.deque).ptr_at(self.end)))
+        }
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> ExactSizeIterator for Drain<'a, T, CAP> {}
+
+impl<'a, T: 'a, const CAP: usize> Drop for Drain<'a, T, CAP> {
+    fn drop(&mut self) {
+        // len is currently the start of the range, so panicking while dropping
+        // will not cause a double drop.
+
+        // exhaust self first
+        self.for_each(drop);
+
+        unsafe {
+            let deque = &mut *self.deque;
+            let start = deque.len();
+            let tail_start = self.tail_start;
+            let tail_len = self.tail_len;
+            let gap = tail_start - start;
+            if start <= tail_len {
+                // move the front part up to meet the tail
+                deque.copy_within(0, gap, start);
+                deque.head = deque.to_physical(gap) as u32;
+            } else {
+                // move the tail down to meet the front part
+                deque.copy_within(tail_start, start, tail_len);
+            }
+            deque.len = (start + tail_len) as u32;
+        }
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<T: Serialize, const CAP: usize> Serialize for ArrayDeque<T, CAP> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayDeque<T, CAP> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        use serde::de::{Visitor, SeqAccess, Error};
+
+        struct ArrayDequeVisitor<'de, T: Deserialize<'de>, const CAP: usize>(PhantomData<(&'de (), [T; CAP])>);
+
+        impl<'de, T: Deserialize<'de>, const CAP: usize> Visitor<'de> for ArrayDequeVisitor<'de, T, CAP> {
+            type Value = ArrayDeque<T, CAP>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "an array with no more than {} items", CAP)
+            }
+
+            fn visit_seq<SA>(self, mut seq: SA) -> Result<Self::Value, SA::Error>
+                where SA: SeqAccess<'de>,
+            {
+                let mut values = ArrayDeque::<T, CAP>::new();
+
+                while let Some(value) = seq.next_element()? {
+                    if values.try_push_back(value).is_err() {
+                        return Err(SA::Error::invalid_length(CAP + 1, &self));
+                    }
+                }
+
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_seq(ArrayDequeVisitor::<T, CAP>(PhantomData))
+    }
+}
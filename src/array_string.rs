@@ -1,40 +1,56 @@
+//! A fixed capacity string, [`ArrayString`], and its iterators and helper types.
+
 use std::borrow::{Borrow, BorrowMut};
 use std::cmp;
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
-use std::mem::MaybeUninit;
-use std::ops::{Deref, DerefMut};
+use std::iter::FromIterator;
+use std::mem::{self, MaybeUninit};
+use std::ops::{Deref, DerefMut, RangeBounds};
 use std::ptr;
 use std::slice;
 use std::str;
 use std::str::FromStr;
 use std::str::Utf8Error;
 
+use crate::ArrayStringView;
+use crate::ArrayVec;
 use crate::CapacityError;
+use crate::errors::{FromUtf8Error, FromUtf16Error};
 use crate::LenUint;
+use crate::len_uint::len_to_usize;
+use crate::arrayvec::resolve_range;
 use crate::char::encode_utf8;
-use crate::utils::MakeMaybeUninit;
+use crate::utils::{CapacityFits, MakeMaybeUninit};
 
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
+#[cfg(feature="alloc")]
+use alloc::{borrow::Cow, boxed::Box, string::String};
+
 
 /// A string with a fixed capacity.
 ///
 /// The `ArrayString` is a string backed by a fixed size array. It keeps track
-/// of its length, and is parameterized by `CAP` for the maximum capacity.
+/// of its length, and is parameterized by `CAP` for the maximum capacity and
+/// `L` for the integer type that stores the length, which defaults to `u32`.
 ///
-/// `CAP` is of type `usize` but is range limited to `u32::MAX`; attempting to create larger
-/// arrayvecs with larger capacity will panic.
+/// `CAP` is of type `usize` but is range limited to [`L::MAX`](LenUint::MAX); attempting to
+/// create larger arrayvecs with larger capacity will panic. A smaller length type makes the
+/// `ArrayString` smaller, for example `ArrayString<15, u8>` is 16 bytes.
 ///
 /// The string is a contiguous value that you can store directly on the stack
 /// if needed.
 #[derive(Copy)]
-pub struct ArrayString<const CAP: usize> {
+// `repr(C)` so that the layout matches `ArrayVec<u8, CAP, L>`, see `as_mut_vec`
+// and `as_view`
+#[repr(C)]
+pub struct ArrayString<const CAP: usize, L: LenUint = u32> {
+    len: L,
     // the `len` first elements of the array are initialized
     xs: [MaybeUninit<u8>; CAP],
-    len: LenUint,
 }
 
 impl<const CAP: usize> Default for ArrayString<CAP>
@@ -45,6 +61,8 @@ impl<const CAP: usize> Default for ArrayString<CAP>
     }
 }
 
+// The length type does not take part in inference, so `new` is only for the
+// default one, like `Vec::new` is only for the global allocator.
 impl<const CAP: usize> ArrayString<CAP>
 {
     /// Create a new empty `ArrayString`.
@@ -60,10 +78,7 @@ impl<const CAP: usize> ArrayString<CAP>
     /// assert_eq!(string.capacity(), 16);
     /// ```
     pub fn new() -> ArrayString<CAP> {
-        assert_capacity_limit!(CAP);
-        unsafe {
-            ArrayString { xs: MaybeUninit::uninit().assume_init(), len: 0 }
-        }
+        ArrayString::new_with_len_type()
     }
 
     /// Create a new empty `ArrayString` (const fn).
@@ -76,13 +91,48 @@ impl<const CAP: usize> ArrayString<CAP>
     /// static ARRAY: ArrayString<1024> = ArrayString::new_const();
     /// ```
     pub const fn new_const() -> ArrayString<CAP> {
-        assert_capacity_limit_const!(CAP);
-        ArrayString { xs: MakeMaybeUninit::ARRAY, len: 0 }
+        ArrayString::new_with_len_type_const()
+    }
+}
+
+impl<const CAP: usize, L: LenUint> ArrayString<CAP, L>
+{
+    /// Create a new empty `ArrayString` with the length type `L`.
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<15, u8>::new_with_len_type();
+    /// string.push_str("foo");
+    /// assert_eq!(&string[..], "foo");
+    /// assert_eq!(std::mem::size_of_val(&string), 16);
+    /// ```
+    pub fn new_with_len_type() -> ArrayString<CAP, L> {
+        assert_capacity_limit!(CAP, L);
+        unsafe {
+            ArrayString { xs: MaybeUninit::uninit().assume_init(), len: L::ZERO }
+        }
+    }
+
+    /// Create a new empty `ArrayString` with the length type `L` (const fn).
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// static ARRAY: ArrayString<255, u8> = ArrayString::new_with_len_type_const();
+    /// ```
+    pub const fn new_with_len_type_const() -> ArrayString<CAP, L> {
+        assert_capacity_limit_const!(CAP, L);
+        ArrayString { xs: MakeMaybeUninit::ARRAY, len: L::ZERO }
     }
 
     /// Return the length of the string.
     #[inline]
-    pub const fn len(&self) -> usize { self.len as usize }
+    pub const fn len(&self) -> usize { len_to_usize(&self.len) }
 
     /// Returns whether the string is empty.
     #[inline]
@@ -103,30 +153,167 @@ impl<const CAP: usize> ArrayString<CAP>
     /// assert_eq!(string.capacity(), 3);
     /// ```
     pub fn from(s: &str) -> Result<Self, CapacityError<&str>> {
-        let mut arraystr = Self::new();
+        let mut arraystr = Self::new_with_len_type();
         arraystr.try_push_str(s)?;
         Ok(arraystr)
     }
 
-    /// Create a new `ArrayString` from a byte string literal.
+    /// Create a new `ArrayString` from a slice of UTF-8 bytes.
     ///
-    /// **Errors** if the byte string literal is not valid UTF-8.
+    /// **Errors** if the bytes are not valid UTF-8, or if they do not fit in
+    /// the capacity.
     ///
     /// ```
-    /// use arrayvec::ArrayString;
+    /// use arrayvec::{ArrayString, FromUtf8Error};
     ///
-    /// let string = ArrayString::from_byte_string(b"hello world").unwrap();
+    /// let string = ArrayString::<8>::from_utf8(b"hello").unwrap();
+    /// assert_eq!(&string[..], "hello");
+    ///
+    /// assert!(matches!(ArrayString::<8>::from_utf8(b"\xffoo"), Err(FromUtf8Error::Utf8(_))));
+    /// assert!(matches!(ArrayString::<2>::from_utf8(b"foo"), Err(FromUtf8Error::Capacity(_))));
     /// ```
-    pub fn from_byte_string(b: &[u8; CAP]) -> Result<Self, Utf8Error> {
-        let len = str::from_utf8(b)?.len();
-        debug_assert_eq!(len, CAP);
-        let mut vec = Self::new();
+    pub fn from_utf8(v: &[u8]) -> Result<Self, FromUtf8Error> {
+        let s = str::from_utf8(v).map_err(FromUtf8Error::Utf8)?;
+        Self::from(s).map_err(|e| FromUtf8Error::Capacity(e.simplify()))
+    }
+
+    /// Create a new `ArrayString` from an `ArrayVec` of UTF-8 bytes, without
+    /// copying.
+    ///
+    /// **Errors** if the bytes are not valid UTF-8.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayString, ArrayVec};
+    ///
+    /// let mut bytes = ArrayVec::<u8, 8>::new();
+    /// bytes.try_extend_from_slice(b"hello").unwrap();
+    /// let string = ArrayString::from_utf8_vec(bytes).unwrap();
+    /// assert_eq!(&string[..], "hello");
+    /// ```
+    pub fn from_utf8_vec(vec: ArrayVec<u8, CAP, L>) -> Result<Self, Utf8Error> {
+        str::from_utf8(&vec)?;
+        let mut string = Self::new_with_len_type();
+        // SAFETY: the bytes were checked to be valid UTF-8
         unsafe {
-            (b as *const [u8; CAP] as *const [MaybeUninit<u8>; CAP])
-                .copy_to_nonoverlapping(&mut vec.xs as *mut [MaybeUninit<u8>; CAP], 1);
-            vec.set_len(CAP);
+            *string.as_mut_vec() = vec;
         }
-        Ok(vec)
+        Ok(string)
+    }
+
+    /// Create a new `ArrayString` from a slice of bytes, replacing invalid
+    /// UTF-8 sequences with U+FFFD REPLACEMENT CHARACTER (�).
+    ///
+    /// The string is truncated at a character boundary if it does not fit in
+    /// the capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<16>::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    /// assert_eq!(&string[..], "Hello �World");
+    ///
+    /// let string = ArrayString::<8>::from_utf8_lossy(b"Hello \xF0\x90\x80World");
+    /// assert_eq!(&string[..], "Hello ");
+    /// ```
+    pub fn from_utf8_lossy(mut v: &[u8]) -> Self {
+        let mut string = Self::new_with_len_type();
+        loop {
+            let (valid, error) = match str::from_utf8(v) {
+                Ok(valid) => (valid, None),
+                Err(error) => {
+                    // SAFETY: the bytes up to `valid_up_to` were checked to be valid UTF-8
+                    let valid = unsafe { str::from_utf8_unchecked(&v[..error.valid_up_to()]) };
+                    (valid, Some(error))
+                }
+            };
+            if string.try_push_str(valid).is_err() {
+                let rest = floor_char_boundary(valid, string.remaining_capacity());
+                string.push_str(&valid[..rest]);
+                break;
+            }
+            let error = match error {
+                Some(error) => error,
+                None => break,
+            };
+            if string.try_push('\u{FFFD}').is_err() {
+                break;
+            }
+            match error.error_len() {
+                Some(len) => v = &v[error.valid_up_to() + len..],
+                None => break,
+            }
+        }
+        string
+    }
+
+    /// Create a new `ArrayString` from a slice of UTF-16 code units.
+    ///
+    /// **Errors** if the input contains an unpaired surrogate, or if the
+    /// decoded string does not fit in the capacity.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayString, FromUtf16Error};
+    ///
+    /// let music = [0xD834, 0xDD1E, 0x006d, 0x0075, 0x0073, 0x0069, 0x0063];
+    /// let string = ArrayString::<16>::from_utf16(&music).unwrap();
+    /// assert_eq!(&string[..], "𝄞music");
+    ///
+    /// let invalid = [0xD834, 0x006d];
+    /// assert_eq!(ArrayString::<8>::from_utf16(&invalid), Err(FromUtf16Error::Utf16));
+    /// assert!(matches!(ArrayString::<4>::from_utf16(&music), Err(FromUtf16Error::Capacity(_))));
+    /// ```
+    pub fn from_utf16(v: &[u16]) -> Result<Self, FromUtf16Error> {
+        let mut string = Self::new_with_len_type();
+        for c in char::decode_utf16(v.iter().cloned()) {
+            let c = c.map_err(|_| FromUtf16Error::Utf16)?;
+            string.try_push(c).map_err(|e| FromUtf16Error::Capacity(e.simplify()))?;
+        }
+        Ok(string)
+    }
+
+    /// Create a new `ArrayString` from a slice of UTF-16 code units, replacing
+    /// unpaired surrogates with U+FFFD REPLACEMENT CHARACTER (�).
+    ///
+    /// The string is truncated at a character boundary if it does not fit in
+    /// the capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let music = [0xD834, 0xDD1E, 0x006d, 0x0075, 0xDD1E, 0x0069, 0x0063];
+    /// let string = ArrayString::<16>::from_utf16_lossy(&music);
+    /// assert_eq!(&string[..], "𝄞mu\u{FFFD}ic");
+    ///
+    /// let string = ArrayString::<8>::from_utf16_lossy(&music);
+    /// assert_eq!(&string[..], "𝄞mu");
+    /// ```
+    pub fn from_utf16_lossy(v: &[u16]) -> Self {
+        let mut string = Self::new_with_len_type();
+        for c in char::decode_utf16(v.iter().cloned()) {
+            if string.try_push(c.unwrap_or('\u{FFFD}')).is_err() {
+                break;
+            }
+        }
+        string
+    }
+
+    /// Create a new `ArrayString` from a slice of bytes, without checking that
+    /// they are valid UTF-8.
+    ///
+    /// ***Panics*** if the bytes do not fit in the capacity.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be valid UTF-8.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = unsafe { ArrayString::<8>::from_utf8_unchecked(b"hello") };
+    /// assert_eq!(&string[..], "hello");
+    /// ```
+    pub unsafe fn from_utf8_unchecked(v: &[u8]) -> Self {
+        Self::from(str::from_utf8_unchecked(v)).unwrap()
     }
 
     /// Create a new `ArrayString` value fully filled with ASCII NULL characters (`\0`). Useful
@@ -140,13 +327,13 @@ impl<const CAP: usize> ArrayString<CAP>
     /// ```
     #[inline]
     pub fn zero_filled() -> Self {
-        assert_capacity_limit!(CAP);
+        assert_capacity_limit!(CAP, L);
         // SAFETY: `assert_capacity_limit` asserts that `len` won't overflow and
         // `zeroed` fully fills the array with nulls.
         unsafe {
             ArrayString {
                 xs: MaybeUninit::zeroed().assume_init(),
-                len: CAP as _
+                len: L::from_usize(CAP)
             }
         }
     }
@@ -384,127 +571,860 @@ impl<const CAP: usize> ArrayString<CAP>
         ch
     }
 
-    /// Make the string empty.
-    pub fn clear(&mut self) {
-        unsafe {
-            self.set_len(0);
-        }
+    /// Inserts a character into this `ArrayString` at a byte position.
+    ///
+    /// This is an `O(n)` operation, as it requires copying every element in the
+    /// array.
+    ///
+    /// ***Panics*** if `idx` is larger than the `ArrayString`’s length, if it does
+    /// not lie on a `char` boundary, or if the backing array is not large enough
+    /// to fit the additional char.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<3>::from("b").unwrap();
+    ///
+    /// s.insert(0, 'a');
+    /// s.insert(2, 'c');
+    /// assert_eq!(&s[..], "abc");
+    /// ```
+    #[track_caller]
+    pub fn insert(&mut self, idx: usize, c: char) {
+        self.try_insert(idx, c).unwrap()
     }
 
-    /// Set the strings’s length.
+    /// Inserts a character into this `ArrayString` at a byte position.
     ///
-    /// This function is `unsafe` because it changes the notion of the
-    /// number of “valid” bytes in the string. Use with care.
+    /// Returns `Ok` if the insertion succeeds.
     ///
-    /// This method uses *debug assertions* to check the validity of `length`
-    /// and may use other debug assertions.
-    pub unsafe fn set_len(&mut self, length: usize) {
-        // type invariant that capacity always fits in LenUint
-        debug_assert!(length <= self.capacity());
-        self.len = length as LenUint;
+    /// **Errors** if the backing array is not large enough to fit the additional char.
+    ///
+    /// ***Panics*** if `idx` is larger than the `ArrayString`’s length, or if it
+    /// does not lie on a `char` boundary.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<3>::from("ab").unwrap();
+    ///
+    /// s.try_insert(1, '-').unwrap();
+    /// let overflow = s.try_insert(1, '-');
+    ///
+    /// assert_eq!(&s[..], "a-b");
+    /// assert_eq!(overflow.unwrap_err().element(), '-');
+    /// ```
+    #[track_caller]
+    pub fn try_insert(&mut self, idx: usize, c: char) -> Result<(), CapacityError<char>> {
+        self.assert_char_boundary("insert", idx);
+        let mut buf = [0; 4];
+        match self.try_insert_str(idx, c.encode_utf8(&mut buf)) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(CapacityError::new(c)),
+        }
     }
 
-    /// Return a string slice of the whole `ArrayString`.
-    pub fn as_str(&self) -> &str {
-        self
+    /// Inserts a string slice into this `ArrayString` at a byte position.
+    ///
+    /// This is an `O(n)` operation, as it requires copying every element in the
+    /// array.
+    ///
+    /// ***Panics*** if `idx` is larger than the `ArrayString`’s length, if it does
+    /// not lie on a `char` boundary, or if the backing array is not large enough
+    /// to fit the string.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<6>::from("bar").unwrap();
+    ///
+    /// s.insert_str(0, "foo");
+    /// assert_eq!(&s[..], "foobar");
+    /// ```
+    #[track_caller]
+    pub fn insert_str(&mut self, idx: usize, s: &str) {
+        self.try_insert_str(idx, s).unwrap()
     }
 
-    /// Return a mutable string slice of the whole `ArrayString`.
-    pub fn as_mut_str(&mut self) -> &mut str {
-        self
+    /// Inserts a string slice into this `ArrayString` at a byte position.
+    ///
+    /// Returns `Ok` if the insertion succeeds.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the string.
+    ///
+    /// ***Panics*** if `idx` is larger than the `ArrayString`’s length, or if it
+    /// does not lie on a `char` boundary.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<6>::from("ad").unwrap();
+    ///
+    /// s.try_insert_str(1, "bc").unwrap();
+    /// let overflow = s.try_insert_str(1, "xyz");
+    ///
+    /// assert_eq!(&s[..], "abcd");
+    /// assert_eq!(overflow.unwrap_err().element(), "xyz");
+    /// ```
+    #[track_caller]
+    pub fn try_insert_str<'a>(&mut self, idx: usize, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        self.assert_char_boundary("insert_str", idx);
+        if s.len() > self.remaining_capacity() {
+            return Err(CapacityError::new(s));
+        }
+        let len = self.len();
+        let amt = s.len();
+        let ptr = self.as_mut_ptr();
+        unsafe {
+            ptr::copy(
+                ptr.add(idx),
+                ptr.add(idx + amt),
+                len - idx);
+            ptr::copy_nonoverlapping(s.as_ptr(), ptr.add(idx), amt);
+            self.set_len(len + amt);
+        }
+        Ok(())
     }
 
-    fn as_ptr(&self) -> *const u8 {
-        self.xs.as_ptr() as *const u8
+    #[track_caller]
+    fn assert_char_boundary(&self, method_name: &str, idx: usize) {
+        if !self.is_char_boundary(idx) {
+            panic!("ArrayString::{}: index {} is not a char boundary in string of length {}",
+                   method_name, idx, self.len());
+        }
     }
 
-    fn as_mut_ptr(&mut self) -> *mut u8 {
-        self.xs.as_mut_ptr() as *mut u8
-    }
-}
+    /// Retains only the characters specified by the predicate.
+    ///
+    /// In other words, remove all characters `c` such that `f(c)` returns false.
+    /// This method operates in place and preserves the order of the retained
+    /// characters.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<16>::from("f_o_ob_ar").unwrap();
+    ///
+    /// s.retain(|c| c != '_');
+    /// assert_eq!(&s[..], "foobar");
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(char) -> bool
+    {
+        // The string is kept valid if `f` panics: the guard sets the length
+        // to cover only the retained characters that were already moved.
+        struct SetLenOnDrop<'a, const CAP: usize, L: LenUint> {
+            s: &'a mut ArrayString<CAP, L>,
+            idx: usize,
+            del_bytes: usize,
+        }
 
-impl<const CAP: usize> Deref for ArrayString<CAP>
-{
-    type Target = str;
-    #[inline]
-    fn deref(&self) -> &str {
-        unsafe {
-            let sl = slice::from_raw_parts(self.as_ptr(), self.len());
-            str::from_utf8_unchecked(sl)
+        impl<const CAP: usize, L: LenUint> Drop for SetLenOnDrop<'_, CAP, L> {
+            fn drop(&mut self) {
+                let new_len = self.idx - self.del_bytes;
+                unsafe { self.s.set_len(new_len) };
+            }
         }
-    }
-}
 
-impl<const CAP: usize> DerefMut for ArrayString<CAP>
-{
-    #[inline]
-    fn deref_mut(&mut self) -> &mut str {
-        unsafe {
-            let len = self.len();
-            let sl = slice::from_raw_parts_mut(self.as_mut_ptr(), len);
-            str::from_utf8_unchecked_mut(sl)
+        let len = self.len();
+        let mut guard = SetLenOnDrop { s: self, idx: 0, del_bytes: 0 };
+
+        while guard.idx < len {
+            let ch = match guard.s[guard.idx..len].chars().next() {
+                Some(ch) => ch,
+                None => break,
+            };
+            let ch_len = ch.len_utf8();
+
+            if !f(ch) {
+                guard.del_bytes += ch_len;
+            } else if guard.del_bytes > 0 {
+                let ptr = guard.s.as_mut_ptr();
+                unsafe {
+                    ptr::copy(
+                        ptr.add(guard.idx),
+                        ptr.add(guard.idx - guard.del_bytes),
+                        ch_len);
+                }
+            }
+
+            guard.idx += ch_len;
         }
+
+        drop(guard);
     }
-}
 
-impl<const CAP: usize> PartialEq for ArrayString<CAP>
-{
-    fn eq(&self, rhs: &Self) -> bool {
-        **self == **rhs
+    /// Create a draining iterator that removes the specified byte range in the
+    /// string and yields the removed characters.
+    ///
+    /// The range is removed when the iterator is dropped, even if it is not
+    /// consumed until the end. If the `Drain` value is leaked, the string is
+    /// not modified.
+    ///
+    /// ***Panics*** if the starting point or end point do not lie on a `char`
+    /// boundary, or if they are out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<16>::from("α is alpha").unwrap();
+    /// let beta_offset = s.find(' ').unwrap();
+    ///
+    /// let t: ArrayString<16> = s.drain(..beta_offset).collect();
+    /// assert_eq!(&t[..], "α");
+    /// assert_eq!(&s[..], " is alpha");
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, CAP, L>
+        where R: RangeBounds<usize>
+    {
+        let (start, end) = resolve_range(range, self.len());
+        // bounds and char boundary checks happen here
+        let chars: *const str = &self[start..end];
+        unsafe {
+            Drain {
+                start,
+                end,
+                iter: (*chars).chars(),
+                string: self as *mut _,
+            }
+        }
     }
-}
 
-impl<const CAP: usize> PartialEq<str> for ArrayString<CAP>
-{
-    fn eq(&self, rhs: &str) -> bool {
-        &**self == rhs
+    /// Replace the specified byte range in the string with the given string slice.
+    ///
+    /// The length of the range does not need to match the length of the string slice.
+    ///
+    /// ***Panics*** if the starting point or end point do not lie on a `char`
+    /// boundary, if they are out of bounds, or if the resulting string does not fit
+    /// in the backing array.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<16>::from("α is alpha").unwrap();
+    /// let beta_offset = s.find(' ').unwrap();
+    ///
+    /// s.replace_range(..beta_offset, "Α");
+    /// assert_eq!(&s[..], "Α is alpha");
+    /// ```
+    #[track_caller]
+    pub fn replace_range<R>(&mut self, range: R, replace_with: &str)
+        where R: RangeBounds<usize>
+    {
+        self.try_replace_range(range, replace_with).unwrap()
     }
-}
 
-impl<const CAP: usize> PartialEq<ArrayString<CAP>> for str
-{
-    fn eq(&self, rhs: &ArrayString<CAP>) -> bool {
-        self == &**rhs
+    /// Replace the specified byte range in the string with the given string slice.
+    ///
+    /// Returns `Ok` if the replacement succeeds.
+    ///
+    /// **Errors** if the resulting string does not fit in the backing array; the
+    /// string is unchanged in that case.
+    ///
+    /// ***Panics*** if the starting point or end point do not lie on a `char`
+    /// boundary, or if they are out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<5>::from("a--b").unwrap();
+    ///
+    /// s.try_replace_range(1..3, "+").unwrap();
+    /// let overflow = s.try_replace_range(1..2, "+++++");
+    ///
+    /// assert_eq!(&s[..], "a+b");
+    /// assert_eq!(overflow.unwrap_err().element(), "+++++");
+    /// ```
+    pub fn try_replace_range<'a, R>(&mut self, range: R, replace_with: &'a str)
+        -> Result<(), CapacityError<&'a str>>
+        where R: RangeBounds<usize>
+    {
+        let len = self.len();
+        let (start, end) = resolve_range(range, len);
+        // bounds and char boundary checks happen here
+        let removed = self[start..end].len();
+        let amt = replace_with.len();
+        if amt > self.remaining_capacity() + removed {
+            return Err(CapacityError::new(replace_with));
+        }
+        let ptr = self.as_mut_ptr();
+        unsafe {
+            ptr::copy(
+                ptr.add(end),
+                ptr.add(start + amt),
+                len - end);
+            ptr::copy_nonoverlapping(replace_with.as_ptr(), ptr.add(start), amt);
+            self.set_len(len - removed + amt);
+        }
+        Ok(())
     }
-}
 
-impl<const CAP: usize> Eq for ArrayString<CAP> 
+    /// Split the string into two at the given byte index.
+    ///
+    /// Return a new `ArrayString` containing the bytes in the range `[at, len)`;
+    /// `self` is left containing the bytes `[0, at)`. The returned string may
+    /// have a different capacity, `CAP2`.
+    ///
+    /// ***Panics*** if `at` does not lie on a `char` boundary, if it is beyond
+    /// the end of the string, or if the split off part does not fit in `CAP2`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut hello = ArrayString::<16>::from("Hello, World!").unwrap();
+    /// let world: ArrayString<6> = hello.split_off(7);
+    /// assert_eq!(&hello[..], "Hello, ");
+    /// assert_eq!(&world[..], "World!");
+    /// ```
+    #[track_caller]
+    pub fn split_off<const CAP2: usize>(&mut self, at: usize) -> ArrayString<CAP2, L> {
+        self.try_split_off(at).unwrap()
+    }
+
+    /// Split the string into two at the given byte index.
+    ///
+    /// Return a new `ArrayString` containing the bytes in the range `[at, len)`;
+    /// `self` is left containing the bytes `[0, at)`. The returned string may
+    /// have a different capacity, `CAP2`.
+    ///
+    /// **Errors** if the split off part does not fit in `CAP2`; the string is
+    /// unchanged in that case.
+    ///
+    /// ***Panics*** if `at` does not lie on a `char` boundary, or if it is beyond
+    /// the end of the string.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<8>::from("foobar").unwrap();
+    /// assert!(s.try_split_off::<2>(3).is_err());
+    /// assert_eq!(&s.try_split_off::<3>(3).unwrap()[..], "bar");
+    /// assert_eq!(&s[..], "foo");
+    /// ```
+    pub fn try_split_off<const CAP2: usize>(&mut self, at: usize) -> Result<ArrayString<CAP2, L>, CapacityError> {
+        let other = ArrayString::from(&self[at..]).map_err(CapacityError::simplify)?;
+        unsafe {
+            self.set_len(at);
+        }
+        Ok(other)
+    }
+
+    /// Convert into an `ArrayString` with capacity `CAP2`.
+    ///
+    /// The new capacity must be at least as large as the current capacity;
+    /// this is checked at compile time.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<3>::from("foo").unwrap();
+    /// let mut t: ArrayString<6> = s.into_capacity();
+    /// t.push_str("bar");
+    /// assert_eq!(&t[..], "foobar");
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<6>::new();
+    /// let t: ArrayString<3> = s.into_capacity();
+    /// ```
+    pub fn into_capacity<const CAP2: usize>(self) -> ArrayString<CAP2, L> {
+        #[allow(clippy::let_unit_value)]
+        let () = CapacityFits::<CAP, CAP2>::ASSERT;
+        let mut s = ArrayString::new_with_len_type();
+        s.push_str(&self);
+        s
+    }
+
+    /// Convert into an `ArrayString` with capacity `CAP2`, if the string fits.
+    ///
+    /// Return an `Ok` value with the new string if the length is at most `CAP2`,
+    /// return an `Err` with self otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<16>::from("foo").unwrap();
+    /// let t = s.try_into_capacity::<3>().unwrap();
+    /// assert_eq!(&t[..], "foo");
+    /// assert!(t.try_into_capacity::<2>().is_err());
+    /// ```
+    pub fn try_into_capacity<const CAP2: usize>(self) -> Result<ArrayString<CAP2, L>, Self> {
+        let mut s = ArrayString::new_with_len_type();
+        match s.try_push_str(&self) {
+            Ok(()) => Ok(s),
+            Err(_) => Err(self),
+        }
+    }
+
+    /// Return a `fmt::Write` adapter that truncates instead of failing when
+    /// the string is full.
+    ///
+    /// See [`TruncatingWriter`] for details.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    /// use std::fmt::Write;
+    ///
+    /// let mut string = ArrayString::<8>::new();
+    /// let mut w = string.truncating();
+    /// write!(w, "{}", "hello world").unwrap();
+    /// assert!(w.is_truncated());
+    /// assert_eq!(&string[..], "hello wo");
+    /// ```
+    pub fn truncating(&mut self) -> TruncatingWriter<'_, CAP, L> {
+        self.truncating_with("")
+    }
+
+    /// Return a `fmt::Write` adapter that truncates instead of failing when
+    /// the string is full, and then appends `marker`.
+    ///
+    /// Room for the marker is made by removing characters from the end, so
+    /// the marker is always appended in full (unless it is longer than the
+    /// capacity, in which case it is itself truncated).
+    ///
+    /// See [`TruncatingWriter`] for details.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    /// use std::fmt::Write;
+    ///
+    /// let mut string = ArrayString::<12>::new();
+    /// let mut w = string.truncating_with("…");
+    /// write!(w, "{}: {}", "error", "disk full").unwrap();
+    /// assert!(w.is_truncated());
+    /// assert_eq!(&string[..], "error: di…");
+    /// ```
+    pub fn truncating_with<'a>(&'a mut self, marker: &'a str) -> TruncatingWriter<'a, CAP, L> {
+        TruncatingWriter { string: self, marker, truncated: false }
+    }
+
+    /// Append formatted text to the string, truncating it at a `char` boundary
+    /// if it does not fit.
+    ///
+    /// Return `true` if the text was truncated.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<4>::new();
+    /// assert!(!string.write_truncated(format_args!("{}", 12)));
+    /// assert!(string.write_truncated(format_args!("{}", "αβγ")));
+    /// assert_eq!(&string[..], "12α");
+    /// ```
+    pub fn write_truncated(&mut self, args: fmt::Arguments<'_>) -> bool {
+        use fmt::Write;
+        let mut w = self.truncating();
+        // never fails, unless a formatting trait implementation returns an error
+        let _ = w.write_fmt(args);
+        w.is_truncated()
+    }
+
+    /// Append all the items of the iterator, which may be `char`, `&char`,
+    /// `&str` or `ArrayString`.
+    ///
+    /// Returns an error with the first item that does not fit in the remaining
+    /// capacity. The items before it are appended, and the ones after it are
+    /// not consumed.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<8>::new();
+    /// string.try_extend(["ab", "cd"].iter().copied()).unwrap();
+    /// string.try_extend("ef".chars()).unwrap();
+    ///
+    /// let overflow = string.try_extend(["g", "hi", "j"].iter().copied());
+    /// assert_eq!(overflow.unwrap_err().element(), "hi");
+    /// assert_eq!(&string[..], "abcdefg");
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), CapacityError<I::Item>>
+        where I: IntoIterator,
+              I::Item: PushStr,
+    {
+        for item in iter {
+            if item.try_push_to(self).is_err() {
+                return Err(CapacityError::new(item));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a new `ArrayString` from the items of the iterator, which may be
+    /// `char`, `&char`, `&str` or `ArrayString`.
+    ///
+    /// **Errors** with the first item that does not fit in the capacity.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<5>::try_from_iter("hello".chars().rev()).unwrap();
+    /// assert_eq!(&string[..], "olleh");
+    ///
+    /// let overflow = ArrayString::<5>::try_from_iter(["hello", "!"].iter().copied());
+    /// assert_eq!(overflow.unwrap_err().element(), "!");
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, CapacityError<I::Item>>
+        where I: IntoIterator,
+              I::Item: PushStr,
+    {
+        let mut string = Self::new_with_len_type();
+        string.try_extend(iter)?;
+        Ok(string)
+    }
+
+    /// Converts this string to its ASCII lower case equivalent in-place.
+    ///
+    /// ASCII letters ‘A’ to ‘Z’ are mapped to ‘a’ to ‘z’, but non-ASCII letters
+    /// are unchanged.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<8>::from("GRÜßE").unwrap();
+    /// s.make_ascii_lowercase();
+    /// assert_eq!(&s[..], "grÜße");
+    /// ```
+    pub fn make_ascii_lowercase(&mut self) {
+        self.as_mut_str().make_ascii_lowercase()
+    }
+
+    /// Converts this string to its ASCII upper case equivalent in-place.
+    ///
+    /// ASCII letters ‘a’ to ‘z’ are mapped to ‘A’ to ‘Z’, but non-ASCII letters
+    /// are unchanged.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut s = ArrayString::<8>::from("grüße").unwrap();
+    /// s.make_ascii_uppercase();
+    /// assert_eq!(&s[..], "GRüßE");
+    /// ```
+    pub fn make_ascii_uppercase(&mut self) {
+        self.as_mut_str().make_ascii_uppercase()
+    }
+
+    /// Return the lowercase equivalent of this string, as a new `ArrayString`
+    /// with capacity `N`.
+    ///
+    /// Each character is mapped with [`char::to_lowercase`], so characters that
+    /// lowercase to multiple characters are handled; unlike `str::to_lowercase`,
+    /// the context dependent mapping of a word-final ‘Σ’ to ‘ς’ is not applied.
+    ///
+    /// **Errors** if the result does not fit in the capacity `N`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<16>::from("İSTANBUL").unwrap();
+    /// assert_eq!(&s.to_lowercase::<16>().unwrap()[..], "i̇stanbul");
+    /// assert!(s.to_lowercase::<8>().is_err());
+    /// ```
+    pub fn to_lowercase<const N: usize>(&self) -> Result<ArrayString<N, L>, CapacityError> {
+        let mut result = ArrayString::new_with_len_type();
+        result.try_extend(self.chars().flat_map(char::to_lowercase))
+            .map_err(CapacityError::simplify)?;
+        Ok(result)
+    }
+
+    /// Return the uppercase equivalent of this string, as a new `ArrayString`
+    /// with capacity `N`.
+    ///
+    /// Each character is mapped with [`char::to_uppercase`], so characters that
+    /// uppercase to multiple characters are handled.
+    ///
+    /// **Errors** if the result does not fit in the capacity `N`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<8>::from("grüße").unwrap();
+    /// assert_eq!(&s.to_uppercase::<8>().unwrap()[..], "GRÜSSE");
+    /// assert!(s.to_uppercase::<6>().is_err());
+    /// ```
+    pub fn to_uppercase<const N: usize>(&self) -> Result<ArrayString<N, L>, CapacityError> {
+        let mut result = ArrayString::new_with_len_type();
+        result.try_extend(self.chars().flat_map(char::to_uppercase))
+            .map_err(CapacityError::simplify)?;
+        Ok(result)
+    }
+
+    /// Replace all matches of `from` with `to`, returning the result as a new
+    /// `ArrayString` with capacity `N`.
+    ///
+    /// **Errors** if the result does not fit in the capacity `N`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<16>::from("this is old").unwrap();
+    /// assert_eq!(&s.replace::<16>("old", "new").unwrap()[..], "this is new");
+    /// assert_eq!(&s.replace::<16>("is", "").unwrap()[..], "th  old");
+    /// assert!(s.replace::<16>("is", "is is").is_err());
+    /// ```
+    pub fn replace<const N: usize>(&self, from: &str, to: &str) -> Result<ArrayString<N, L>, CapacityError> {
+        let mut result = ArrayString::new_with_len_type();
+        let mut last_end = 0;
+        for (start, part) in self.match_indices(from) {
+            result.try_push_str(&self[last_end..start]).map_err(CapacityError::simplify)?;
+            result.try_push_str(to).map_err(CapacityError::simplify)?;
+            last_end = start + part.len();
+        }
+        result.try_push_str(&self[last_end..]).map_err(CapacityError::simplify)?;
+        Ok(result)
+    }
+
+    /// Return this string repeated `n` times, as a new `ArrayString` with
+    /// capacity `N`.
+    ///
+    /// **Errors** if the result does not fit in the capacity `N`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let s = ArrayString::<2>::from("ab").unwrap();
+    /// assert_eq!(&s.repeat::<8>(3).unwrap()[..], "ababab");
+    /// assert!(s.repeat::<8>(5).is_err());
+    /// ```
+    pub fn repeat<const N: usize>(&self, n: usize) -> Result<ArrayString<N, L>, CapacityError> {
+        match self.len().checked_mul(n) {
+            Some(len) if len <= N => {}
+            _ => return Err(CapacityError::new(())),
+        }
+        let mut result = ArrayString::new_with_len_type();
+        for _ in 0..n {
+            result.push_str(self);
+        }
+        Ok(result)
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        unsafe {
+            self.set_len(0);
+        }
+    }
+
+    /// Set the strings’s length.
+    ///
+    /// This function is `unsafe` because it changes the notion of the
+    /// number of “valid” bytes in the string. Use with care.
+    ///
+    /// This method uses *debug assertions* to check the validity of `length`
+    /// and may use other debug assertions.
+    pub unsafe fn set_len(&mut self, length: usize) {
+        // type invariant that capacity always fits in LenUint
+        debug_assert!(length <= self.capacity());
+        self.len = L::from_usize(length);
+    }
+
+    /// Return a string slice of the whole `ArrayString`.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Return a mutable string slice of the whole `ArrayString`.
+    pub fn as_mut_str(&mut self) -> &mut str {
+        self
+    }
+
+    /// Convert the string into an `ArrayVec` of its UTF-8 bytes.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::<8>::from("hello").unwrap();
+    /// let bytes = string.into_bytes();
+    /// assert_eq!(&bytes[..], b"hello");
+    /// assert_eq!(bytes.capacity(), 8);
+    /// ```
+    pub fn into_bytes(mut self) -> ArrayVec<u8, CAP, L> {
+        let mut vec = ArrayVec::new_with_len_type();
+        // SAFETY: dropping the string does not need the bytes to be valid UTF-8
+        unsafe {
+            mem::swap(self.as_mut_vec(), &mut vec);
+        }
+        vec
+    }
+
+    /// Return a mutable reference to the contents of the string, as an
+    /// `ArrayVec` of bytes.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the bytes are valid UTF-8 before the
+    /// borrow ends and the `ArrayString` is used.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<8>::from("hello").unwrap();
+    /// unsafe {
+    ///     let vec = string.as_mut_vec();
+    ///     vec.reverse();
+    /// }
+    /// assert_eq!(&string[..], "olleh");
+    /// ```
+    pub unsafe fn as_mut_vec(&mut self) -> &mut ArrayVec<u8, CAP, L> {
+        // SAFETY: both types are `repr(C)` with identical fields
+        &mut *(self as *mut Self as *mut ArrayVec<u8, CAP, L>)
+    }
+
+    /// Return a capacity-erased view of the string.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayString, ArrayStringView};
+    ///
+    /// fn shout(s: &ArrayStringView) -> bool {
+    ///     s.ends_with('!')
+    /// }
+    ///
+    /// let string = ArrayString::<8>::from("hello!").unwrap();
+    /// assert!(shout(string.as_view()));
+    /// assert_eq!(string.as_view().capacity(), 8);
+    /// ```
+    pub fn as_view(&self) -> &ArrayStringView<L> {
+        // SAFETY: `ArrayStringView<L>` has the layout of `ArrayString<CAP, L>`
+        unsafe { ArrayStringView::from_raw(self as *const Self as *const u8, CAP) }
+    }
+
+    /// Return a mutable capacity-erased view of the string.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayString, ArrayStringView};
+    ///
+    /// fn exclaim(s: &mut ArrayStringView) {
+    ///     let _ = s.try_push('!');
+    /// }
+    ///
+    /// let mut string = ArrayString::<8>::from("hello").unwrap();
+    /// exclaim(string.as_mut_view());
+    /// assert_eq!(&string[..], "hello!");
+    /// ```
+    pub fn as_mut_view(&mut self) -> &mut ArrayStringView<L> {
+        // SAFETY: `ArrayStringView<L>` has the layout of `ArrayString<CAP, L>`
+        unsafe { ArrayStringView::from_raw_mut(self as *mut Self as *mut u8, CAP) }
+    }
+
+    fn as_ptr(&self) -> *const u8 {
+        self.xs.as_ptr() as *const u8
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.xs.as_mut_ptr() as *mut u8
+    }
+}
+
+// Constructors that infer the capacity from their argument can not infer the
+// length type, so they use the default one.
+impl<const CAP: usize> ArrayString<CAP>
+{
+    /// Create a new `ArrayString` from a byte string literal.
+    ///
+    /// **Errors** if the byte string literal is not valid UTF-8.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let string = ArrayString::from_byte_string(b"hello world").unwrap();
+    /// ```
+    pub fn from_byte_string(b: &[u8; CAP]) -> Result<Self, Utf8Error> {
+        let len = str::from_utf8(b)?.len();
+        debug_assert_eq!(len, CAP);
+        let mut vec = Self::new();
+        unsafe {
+            (b as *const [u8; CAP] as *const [MaybeUninit<u8>; CAP])
+                .copy_to_nonoverlapping(&mut vec.xs as *mut [MaybeUninit<u8>; CAP], 1);
+            vec.set_len(CAP);
+        }
+        Ok(vec)
+    }
+}
+
+impl<const CAP: usize, L: LenUint> Deref for ArrayString<CAP, L>
+{
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        unsafe {
+            let sl = slice::from_raw_parts(self.as_ptr(), self.len());
+            str::from_utf8_unchecked(sl)
+        }
+    }
+}
+
+impl<const CAP: usize, L: LenUint> DerefMut for ArrayString<CAP, L>
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        unsafe {
+            let len = self.len();
+            let sl = slice::from_raw_parts_mut(self.as_mut_ptr(), len);
+            str::from_utf8_unchecked_mut(sl)
+        }
+    }
+}
+
+impl<const CAP: usize, L: LenUint> PartialEq for ArrayString<CAP, L>
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<const CAP: usize, L: LenUint> PartialEq<str> for ArrayString<CAP, L>
+{
+    fn eq(&self, rhs: &str) -> bool {
+        &**self == rhs
+    }
+}
+
+impl<const CAP: usize, L: LenUint> PartialEq<ArrayString<CAP, L>> for str
+{
+    fn eq(&self, rhs: &ArrayString<CAP, L>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl<const CAP: usize, L: LenUint> Eq for ArrayString<CAP, L> 
 { }
 
-impl<const CAP: usize> Hash for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> Hash for ArrayString<CAP, L>
 {
     fn hash<H: Hasher>(&self, h: &mut H) {
         (**self).hash(h)
     }
 }
 
-impl<const CAP: usize> Borrow<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> Borrow<str> for ArrayString<CAP, L>
 {
     fn borrow(&self) -> &str { self }
 }
 
-impl<const CAP: usize> BorrowMut<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> BorrowMut<str> for ArrayString<CAP, L>
 {
     fn borrow_mut(&mut self) -> &mut str { self }
 }
 
-impl<const CAP: usize> AsRef<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> AsRef<str> for ArrayString<CAP, L>
 {
     fn as_ref(&self) -> &str { self }
 }
 
-impl<const CAP: usize> fmt::Debug for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> fmt::Debug for ArrayString<CAP, L>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
-impl<const CAP: usize> fmt::Display for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> fmt::Display for ArrayString<CAP, L>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
 /// `Write` appends written data to the end of the string.
-impl<const CAP: usize> fmt::Write for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> fmt::Write for ArrayString<CAP, L>
 {
     fn write_char(&mut self, c: char) -> fmt::Result {
         self.try_push(c).map_err(|_| fmt::Error)
@@ -515,9 +1435,69 @@ impl<const CAP: usize> fmt::Write for ArrayString<CAP>
     }
 }
 
-impl<const CAP: usize> Clone for ArrayString<CAP>
+/// A `fmt::Write` adapter for [`ArrayString`] that truncates instead of failing
+/// when the string is full.
+///
+/// Created by [`ArrayString::truncating`] and [`ArrayString::truncating_with`].
+///
+/// Text is appended until the first write that does not fit. That write is
+/// truncated at the last `char` boundary that fits, followed by the marker
+/// if there is one, and all further writes are ignored. Writing never returns
+/// an error; use [`is_truncated`](TruncatingWriter::is_truncated) to find out
+/// if truncation happened.
+pub struct TruncatingWriter<'a, const CAP: usize, L: LenUint = u32> {
+    string: &'a mut ArrayString<CAP, L>,
+    marker: &'a str,
+    truncated: bool,
+}
+
+impl<'a, const CAP: usize, L: LenUint> TruncatingWriter<'a, CAP, L> {
+    /// Return `true` if any written text was truncated.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+impl<'a, const CAP: usize, L: LenUint> fmt::Write for TruncatingWriter<'a, CAP, L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.truncated || self.string.try_push_str(s).is_ok() {
+            return Ok(());
+        }
+        self.truncated = true;
+        let marker = &self.marker[..floor_char_boundary(self.marker, CAP)];
+        // the room for text, with the marker appended after it
+        let limit = CAP - marker.len();
+        let len = self.string.len();
+        if len > limit {
+            let new_len = floor_char_boundary(self.string, limit);
+            self.string.truncate(new_len);
+        } else {
+            self.string.push_str(&s[..floor_char_boundary(s, limit - len)]);
+        }
+        self.string.push_str(marker);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// Return the largest `char` boundary in `s` that is at most `index`.
+fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    let mut i = index;
+    while !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+impl<const CAP: usize, L: LenUint> Clone for ArrayString<CAP, L>
 {
-    fn clone(&self) -> ArrayString<CAP> {
+    fn clone(&self) -> ArrayString<CAP, L> {
         *self
     }
     fn clone_from(&mut self, rhs: &Self) {
@@ -527,7 +1507,7 @@ impl<const CAP: usize> Clone for ArrayString<CAP>
     }
 }
 
-impl<const CAP: usize> PartialOrd for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> PartialOrd for ArrayString<CAP, L>
 {
     fn partial_cmp(&self, rhs: &Self) -> Option<cmp::Ordering> {
         (**self).partial_cmp(&**rhs)
@@ -538,7 +1518,7 @@ impl<const CAP: usize> PartialOrd for ArrayString<CAP>
     fn ge(&self, rhs: &Self) -> bool { **self >= **rhs }
 }
 
-impl<const CAP: usize> PartialOrd<str> for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> PartialOrd<str> for ArrayString<CAP, L>
 {
     fn partial_cmp(&self, rhs: &str) -> Option<cmp::Ordering> {
         (**self).partial_cmp(rhs)
@@ -549,25 +1529,178 @@ impl<const CAP: usize> PartialOrd<str> for ArrayString<CAP>
     fn ge(&self, rhs: &str) -> bool { &**self >= rhs }
 }
 
-impl<const CAP: usize> PartialOrd<ArrayString<CAP>> for str
+impl<const CAP: usize, L: LenUint> PartialOrd<ArrayString<CAP, L>> for str
 {
-    fn partial_cmp(&self, rhs: &ArrayString<CAP>) -> Option<cmp::Ordering> {
+    fn partial_cmp(&self, rhs: &ArrayString<CAP, L>) -> Option<cmp::Ordering> {
         self.partial_cmp(&**rhs)
     }
-    fn lt(&self, rhs: &ArrayString<CAP>) -> bool { self < &**rhs }
-    fn le(&self, rhs: &ArrayString<CAP>) -> bool { self <= &**rhs }
-    fn gt(&self, rhs: &ArrayString<CAP>) -> bool { self > &**rhs }
-    fn ge(&self, rhs: &ArrayString<CAP>) -> bool { self >= &**rhs }
+    fn lt(&self, rhs: &ArrayString<CAP, L>) -> bool { self < &**rhs }
+    fn le(&self, rhs: &ArrayString<CAP, L>) -> bool { self <= &**rhs }
+    fn gt(&self, rhs: &ArrayString<CAP, L>) -> bool { self > &**rhs }
+    fn ge(&self, rhs: &ArrayString<CAP, L>) -> bool { self >= &**rhs }
 }
 
-impl<const CAP: usize> Ord for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> Ord for ArrayString<CAP, L>
 {
     fn cmp(&self, rhs: &Self) -> cmp::Ordering {
         (**self).cmp(&**rhs)
     }
 }
 
-impl<const CAP: usize> FromStr for ArrayString<CAP>
+/// A draining iterator for `ArrayString`.
+///
+/// Created by [`ArrayString::drain`].
+pub struct Drain<'a, const CAP: usize, L: LenUint = u32> {
+    /// Start of the removed range
+    start: usize,
+    /// End of the removed range
+    end: usize,
+    /// Remaining characters of the removed range
+    iter: str::Chars<'a>,
+    string: *mut ArrayString<CAP, L>,
+}
+
+unsafe impl<'a, const CAP: usize, L: LenUint> Sync for Drain<'a, CAP, L> {}
+unsafe impl<'a, const CAP: usize, L: LenUint> Send for Drain<'a, CAP, L> {}
+
+impl<'a, const CAP: usize, L: LenUint> Drain<'a, CAP, L> {
+    /// Return the remaining (not yet yielded) part of the removed range.
+    pub fn as_str(&self) -> &str {
+        self.iter.as_str()
+    }
+}
+
+impl<'a, const CAP: usize, L: LenUint> Iterator for Drain<'a, CAP, L> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, const CAP: usize, L: LenUint> DoubleEndedIterator for Drain<'a, CAP, L> {
+    fn next_back(&mut self) -> Option<char> {
+        self.iter.next_back()
+    }
+}
+
+impl<'a, const CAP: usize, L: LenUint> fmt::Debug for Drain<'a, CAP, L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.as_str()).finish()
+    }
+}
+
+impl<'a, const CAP: usize, L: LenUint> Drop for Drain<'a, CAP, L> {
+    fn drop(&mut self) {
+        unsafe {
+            let string = &mut *self.string;
+            // memmove back the tail, update to new length
+            let len = string.len();
+            let ptr = string.as_mut_ptr();
+            ptr::copy(ptr.add(self.end), ptr.add(self.start), len - self.end);
+            string.set_len(len - (self.end - self.start));
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A value that can be appended to an [`ArrayString`]: `char`, `&char`, `&str`
+/// or `ArrayString`.
+///
+/// This is the item type accepted by the `Extend` and `FromIterator`
+/// implementations of `ArrayString`, and by [`ArrayString::try_extend`].
+/// This trait is sealed and cannot be implemented outside of `arrayvec`.
+pub trait PushStr: private::Sealed {
+    #[doc(hidden)]
+    fn try_push_to<const CAP: usize, L: LenUint>(&self, string: &mut ArrayString<CAP, L>) -> Result<(), CapacityError>;
+}
+
+impl private::Sealed for char {}
+
+impl PushStr for char {
+    fn try_push_to<const CAP: usize, L: LenUint>(&self, string: &mut ArrayString<CAP, L>) -> Result<(), CapacityError> {
+        string.try_push(*self).map_err(CapacityError::simplify)
+    }
+}
+
+impl private::Sealed for &char {}
+
+impl PushStr for &char {
+    fn try_push_to<const CAP: usize, L: LenUint>(&self, string: &mut ArrayString<CAP, L>) -> Result<(), CapacityError> {
+        string.try_push(**self).map_err(CapacityError::simplify)
+    }
+}
+
+impl private::Sealed for &str {}
+
+impl PushStr for &str {
+    fn try_push_to<const CAP: usize, L: LenUint>(&self, string: &mut ArrayString<CAP, L>) -> Result<(), CapacityError> {
+        string.try_push_str(self).map_err(CapacityError::simplify)
+    }
+}
+
+impl<const N: usize, L: LenUint> private::Sealed for ArrayString<N, L> {}
+
+impl<const N: usize, L2: LenUint> PushStr for ArrayString<N, L2> {
+    fn try_push_to<const CAP: usize, L: LenUint>(&self, string: &mut ArrayString<CAP, L>) -> Result<(), CapacityError> {
+        string.try_push_str(self).map_err(CapacityError::simplify)
+    }
+}
+
+/// Extend the `ArrayString` with an iterator of `char`, `&char`, `&str`
+/// or `ArrayString`.
+///
+/// ***Panics*** if extending the string exceeds its capacity.
+impl<T: PushStr, const CAP: usize, L: LenUint> Extend<T> for ArrayString<CAP, L> {
+    /// Extend the `ArrayString` with an iterator.
+    ///
+    /// ***Panics*** if extending the string exceeds its capacity.
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        if self.try_extend(iter).is_err() {
+            extend_panic();
+        }
+    }
+}
+
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn extend_panic() {
+    panic!("ArrayString: capacity exceeded in extend/from_iter");
+}
+
+/// Create an `ArrayString` from an iterator of `char`, `&char`, `&str`
+/// or `ArrayString`.
+///
+/// ***Panics*** if the string built from the iterator exceeds the capacity.
+///
+/// ```
+/// use arrayvec::ArrayString;
+///
+/// let string: ArrayString<16> = "hello world".split(' ').collect();
+/// assert_eq!(&string[..], "helloworld");
+/// ```
+impl<T: PushStr, const CAP: usize, L: LenUint> FromIterator<T> for ArrayString<CAP, L> {
+    /// Create an `ArrayString` from an iterator.
+    ///
+    /// ***Panics*** if the string built from the iterator exceeds the capacity.
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut string = Self::new_with_len_type();
+        string.extend(iter);
+        string
+    }
+}
+
+impl<const CAP: usize, L: LenUint> FromStr for ArrayString<CAP, L>
 {
     type Err = CapacityError;
 
@@ -578,7 +1711,7 @@ impl<const CAP: usize> FromStr for ArrayString<CAP>
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<const CAP: usize> Serialize for ArrayString<CAP>
+impl<const CAP: usize, L: LenUint> Serialize for ArrayString<CAP, L>
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
@@ -589,7 +1722,7 @@ impl<const CAP: usize> Serialize for ArrayString<CAP>
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP> 
+impl<'de, const CAP: usize, L: LenUint> Deserialize<'de> for ArrayString<CAP, L> 
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>
@@ -597,10 +1730,10 @@ impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP>
         use serde::de::{self, Visitor};
         use std::marker::PhantomData;
 
-        struct ArrayStringVisitor<const CAP: usize>(PhantomData<[u8; CAP]>);
+        struct ArrayStringVisitor<const CAP: usize, L>(PhantomData<([u8; CAP], L)>);
 
-        impl<'de, const CAP: usize> Visitor<'de> for ArrayStringVisitor<CAP> {
-            type Value = ArrayString<CAP>;
+        impl<'de, const CAP: usize, L: LenUint> Visitor<'de> for ArrayStringVisitor<CAP, L> {
+            type Value = ArrayString<CAP, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 write!(formatter, "a string no more than {} bytes long", CAP)
@@ -625,29 +1758,106 @@ impl<'de, const CAP: usize> Deserialize<'de> for ArrayString<CAP>
     }
 }
 
-impl<'a, const CAP: usize> TryFrom<&'a str> for ArrayString<CAP>
+impl<'a, const CAP: usize, L: LenUint> TryFrom<&'a str> for ArrayString<CAP, L>
 {
     type Error = CapacityError<&'a str>;
 
     fn try_from(f: &'a str) -> Result<Self, Self::Error> {
-        let mut v = Self::new();
+        let mut v = Self::new_with_len_type();
         v.try_push_str(f)?;
         Ok(v)
     }
 }
 
-impl<'a, const CAP: usize> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP>
+impl<'a, const CAP: usize, L: LenUint> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP, L>
 {
     type Error = CapacityError<fmt::Error>;
 
     fn try_from(f: fmt::Arguments<'a>) -> Result<Self, Self::Error> {
         use fmt::Write;
-        let mut v = Self::new();
+        let mut v = Self::new_with_len_type();
         v.write_fmt(f).map_err(|e| CapacityError::new(e))?;
         Ok(v)
     }
 }
 
+#[cfg(feature="alloc")]
+/// Try to create an `ArrayString` from a `String`, returning the `String` as
+/// the error if it does not fit.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayString;
+/// use std::convert::TryFrom;
+///
+/// let string = ArrayString::<8>::try_from(String::from("hello")).unwrap();
+/// assert_eq!(&string[..], "hello");
+/// let err = ArrayString::<4>::try_from(String::from("hello")).unwrap_err();
+/// assert_eq!(err.element(), "hello");
+/// ```
+impl<const CAP: usize, L: LenUint> TryFrom<String> for ArrayString<CAP, L>
+{
+    type Error = CapacityError<String>;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match Self::from(&s) {
+            Ok(string) => Ok(string),
+            Err(_) => Err(CapacityError::new(s)),
+        }
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Requires `features="alloc"`.
+impl<const CAP: usize, L: LenUint> From<ArrayString<CAP, L>> for String
+{
+    fn from(s: ArrayString<CAP, L>) -> Self {
+        String::from(s.as_str())
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Requires `features="alloc"`.
+impl<const CAP: usize, L: LenUint> From<ArrayString<CAP, L>> for Box<str>
+{
+    fn from(s: ArrayString<CAP, L>) -> Self {
+        Box::from(s.as_str())
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Requires `features="alloc"`.
+impl<'a, const CAP: usize, L: LenUint> From<ArrayString<CAP, L>> for Cow<'a, str>
+{
+    fn from(s: ArrayString<CAP, L>) -> Self {
+        Cow::Owned(s.into())
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Borrow the string as a `Cow`, which is turned into a `String` by
+/// `.into_owned()` through `ToOwned`.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayString;
+/// use std::borrow::Cow;
+///
+/// let string = ArrayString::<8>::from("hello").unwrap();
+/// let cow = Cow::from(&string);
+/// assert!(matches!(cow, Cow::Borrowed("hello")));
+/// let owned: String = cow.into_owned();
+/// assert_eq!(owned, "hello");
+/// ```
+impl<'a, const CAP: usize, L: LenUint> From<&'a ArrayString<CAP, L>> for Cow<'a, str>
+{
+    fn from(s: &'a ArrayString<CAP, L>) -> Self {
+        Cow::Borrowed(s)
+    }
+}
+
 #[cfg(feature = "zeroize")]
 /// "Best efforts" zeroing of the `ArrayString`'s buffer when the `zeroize` feature is enabled.
 ///
@@ -663,7 +1873,7 @@ impl<'a, const CAP: usize> TryFrom<fmt::Arguments<'a>> for ArrayString<CAP>
 /// unsafe { string.set_len(string.capacity()) };
 /// assert_eq!(&*string, "\0\0\0\0\0\0");
 /// ```
-impl<const CAP: usize> zeroize::Zeroize for ArrayString<CAP> {
+impl<const CAP: usize, L: LenUint> zeroize::Zeroize for ArrayString<CAP, L> {
     fn zeroize(&mut self) {
         // There are no elements to drop
         self.clear();
@@ -0,0 +1,98 @@
+
+use std::cmp;
+use std::fmt;
+use std::iter::FusedIterator;
+
+use crate::ArrayVec;
+use crate::utils::CapacityFits;
+
+/// An extension trait for iterators, to collect their elements into batches
+/// of fixed capacity.
+///
+/// It is implemented for all iterators.
+pub trait ArrayVecChunksExt: Iterator {
+    /// Return an iterator of `ArrayVec<Self::Item, CAP>` batches of the
+    /// elements of this iterator, in order.
+    ///
+    /// Every batch is full, except for the last one, which holds the
+    /// remaining elements. It is not yielded if there are none.
+    ///
+    /// The capacity must be at least 1, which is checked at compile time.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecChunksExt;
+    ///
+    /// let mut chunks = (0..7).array_vec_chunks::<3>();
+    /// assert_eq!(&chunks.next().unwrap()[..], &[0, 1, 2]);
+    /// assert_eq!(&chunks.next().unwrap()[..], &[3, 4, 5]);
+    /// assert_eq!(&chunks.next().unwrap()[..], &[6]);
+    /// assert!(chunks.next().is_none());
+    /// ```
+    fn array_vec_chunks<const CAP: usize>(self) -> ArrayVecChunks<Self, CAP>
+        where Self: Sized
+    {
+        ArrayVecChunks::new(self)
+    }
+}
+
+impl<I: Iterator> ArrayVecChunksExt for I { }
+
+/// An iterator that yields the elements of another iterator in
+/// `ArrayVec<I::Item, CAP>` batches.
+///
+/// See [`ArrayVecChunksExt::array_vec_chunks`].
+#[derive(Clone)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ArrayVecChunks<I, const CAP: usize> {
+    iter: I,
+}
+
+impl<I: Iterator, const CAP: usize> ArrayVecChunks<I, CAP> {
+    /// Create an iterator of `ArrayVec<I::Item, CAP>` batches of the elements
+    /// of `iter`.
+    ///
+    /// The capacity must be at least 1, which is checked at compile time.
+    pub fn new(iter: I) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = CapacityFits::<1, CAP>::ASSERT;
+        ArrayVecChunks { iter }
+    }
+
+    /// Return the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator, const CAP: usize> Iterator for ArrayVecChunks<I, CAP> {
+    type Item = ArrayVec<I::Item, CAP>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = ArrayVec::new();
+        unsafe {
+            // at most `CAP` elements are taken, which fit in the empty batch
+            batch.extend_from_iter::<_, false>(self.iter.by_ref().take(CAP));
+        }
+        if batch.is_empty() {
+            None
+        } else {
+            Some(batch)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.iter.size_hint();
+        let batches = |n: usize| n / CAP + cmp::min(n % CAP, 1);
+        (batches(lower), upper.map(batches))
+    }
+}
+
+impl<I: FusedIterator, const CAP: usize> FusedIterator for ArrayVecChunks<I, CAP> { }
+
+impl<I: fmt::Debug, const CAP: usize> fmt::Debug for ArrayVecChunks<I, CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ArrayVecChunks")
+            .field("iter", &self.iter)
+            .finish()
+    }
+}
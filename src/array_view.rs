@@ -0,0 +1,603 @@
+//! Capacity-erased views of an [`ArrayVec`] and an [`ArrayString`],
+//! [`ArrayVecView`] and [`ArrayStringView`], and the view's draining iterator.
+//!
+//! [`ArrayVec`]: crate::ArrayVec
+//! [`ArrayString`]: crate::ArrayString
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut, RangeBounds};
+use std::ptr;
+use std::str;
+
+use crate::LenUint;
+use crate::len_uint::len_to_usize;
+use crate::arrayvec::resolve_range;
+use crate::arrayvec_impl::{ArrayVecImpl, DrainImpl};
+use crate::char::encode_utf8;
+use crate::errors::CapacityError;
+
+/// A capacity-erased view of an [`ArrayVec`](crate::ArrayVec).
+///
+/// An `&mut ArrayVecView<T, L>` is obtained from any `ArrayVec<T, CAP, L>`
+/// with [`as_mut_view`](crate::ArrayVec::as_mut_view). The capacity is only
+/// known at runtime, so a function taking a view is not generic over `CAP`
+/// and is compiled only once for all capacities.
+///
+/// The view has the vector API that does not move the vector itself by value,
+/// and dereferences to a slice.
+///
+/// ```
+/// use arrayvec::{ArrayVec, ArrayVecView};
+///
+/// fn fill(v: &mut ArrayVecView<u32>) {
+///     let mut i = 0;
+///     while v.try_push(i).is_ok() {
+///         i += 1;
+///     }
+/// }
+///
+/// let mut small = ArrayVec::<u32, 2>::new();
+/// let mut large = ArrayVec::<u32, 4>::new();
+/// fill(small.as_mut_view());
+/// fill(large.as_mut_view());
+/// assert_eq!(&small[..], &[0, 1]);
+/// assert_eq!(&large[..], &[0, 1, 2, 3]);
+/// ```
+// `repr(C)` so that the layout matches `ArrayVec<T, CAP, L>`
+#[repr(C)]
+pub struct ArrayVecView<T, L: LenUint = u32> {
+    len: L,
+    // the `len` first elements of the slice are initialized
+    xs: [MaybeUninit<T>],
+}
+
+impl<T, L: LenUint> ArrayVecView<T, L> {
+    /// Create a view from a pointer to an `ArrayVec<T, CAP, L>` and its
+    /// capacity.
+    ///
+    /// Safety: `ptr` must point to an `ArrayVec<T, cap, L>` or a type with the
+    /// same layout, valid for the lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw<'a>(ptr: *const u8, cap: usize) -> &'a Self {
+        &*(ptr::slice_from_raw_parts(ptr as *const MaybeUninit<T>, cap) as *const Self)
+    }
+
+    /// Create a mutable view from a pointer to an `ArrayVec<T, CAP, L>` and its
+    /// capacity.
+    ///
+    /// Safety: `ptr` must point to an `ArrayVec<T, cap, L>` or a type with the
+    /// same layout, valid and unaliased for the lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw_mut<'a>(ptr: *mut u8, cap: usize) -> &'a mut Self {
+        &mut *(ptr::slice_from_raw_parts_mut(ptr as *mut MaybeUninit<T>, cap) as *mut Self)
+    }
+
+    /// Return the number of elements in the view.
+    #[inline]
+    pub fn len(&self) -> usize { ArrayVecImpl::len(self) }
+
+    /// Returns whether the view is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the underlying vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<i32, 3>::new();
+    /// assert_eq!(array.as_view().capacity(), 3);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize { self.xs.len() }
+
+    /// Return true if the vector is completely filled to its capacity, false otherwise.
+    pub fn is_full(&self) -> bool { self.len() == self.capacity() }
+
+    /// Returns the capacity left in the vector.
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// ***Panics*** if the vector is already full.
+    #[track_caller]
+    pub fn push(&mut self, element: T) {
+        ArrayVecImpl::push(self, element)
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// **Errors** if the vector is already full, returning the element.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 1>::new();
+    /// let view = array.as_mut_view();
+    /// assert!(view.try_push(1).is_ok());
+    /// assert_eq!(view.try_push(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        ArrayVecImpl::try_push(self, element)
+    }
+
+    /// Remove the last element in the vector and return it.
+    ///
+    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    pub fn pop(&mut self) -> Option<T> {
+        ArrayVecImpl::pop(self)
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the vector’s current length this has no
+    /// effect.
+    pub fn truncate(&mut self, new_len: usize) {
+        ArrayVecImpl::truncate(self, new_len)
+    }
+
+    /// Remove all elements in the vector.
+    pub fn clear(&mut self) {
+        ArrayVecImpl::clear(self)
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// ***Panics*** if the vector is already full or if `index` is out of
+    /// bounds.
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.try_insert(index, element).unwrap()
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// Shift up all elements after `index`; the `index` must be less than
+    /// or equal to the length.
+    ///
+    /// Returns an error if vector is already at full capacity.
+    ///
+    /// ***Panics*** `index` is out of bounds.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 2>::new();
+    /// let view = array.as_mut_view();
+    ///
+    /// assert!(view.try_insert(0, "x").is_ok());
+    /// assert!(view.try_insert(0, "y").is_ok());
+    /// assert!(view.try_insert(0, "z").is_err());
+    /// assert_eq!(&array[..], &["y", "x"]);
+    /// ```
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), CapacityError<T>> {
+        let len = self.len();
+        if index > len {
+            panic_oob!("ArrayVecView", "try_insert", index, len)
+        }
+        ArrayVecImpl::try_insert(self, index, element)
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.pop_at(index)
+            .unwrap_or_else(|| {
+                panic_oob!("ArrayVecView", "remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// This is a checked version of `.remove(index)`. Returns `None` if there
+    /// is no element at `index`. Otherwise, return the element inside `Some`.
+    pub fn pop_at(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::pop_at(self, index)
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.swap_pop(index)
+            .unwrap_or_else(|| {
+                panic_oob!("ArrayVecView", "swap_remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// This is a checked version of `.swap_remove`.
+    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    pub fn swap_pop(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::swap_pop(self, index)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns false.
+    /// This method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 3, 4]);
+    /// array.as_mut_view().retain(|x| *x & 1 != 0 );
+    /// assert_eq!(&array[..], &[1, 3]);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+        where F: FnMut(&mut T) -> bool
+    {
+        ArrayVecImpl::retain(self, f)
+    }
+
+    /// Copy all elements from the slice and append to the vector.
+    ///
+    /// **Errors** if the capacity left is smaller than the length of the
+    /// slice.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+        where T: Copy,
+    {
+        ArrayVecImpl::try_extend_from_slice(self, other)
+    }
+
+    /// Create a draining iterator that removes the specified range in the vector
+    /// and yields the removed items from start to end. The element range is
+    /// removed even if the iterator is not consumed until the end.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::from([1, 2, 3]);
+    /// let v2: ArrayVec<_, 3> = v1.as_mut_view().drain(0..2).collect();
+    /// assert_eq!(&v1[..], &[3]);
+    /// assert_eq!(&v2[..], &[1, 2]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, L>
+        where R: RangeBounds<usize>
+    {
+        let (start, end) = resolve_range(range, self.len());
+        Drain { inner: ArrayVecImpl::drain_range(self, start, end) }
+    }
+
+    /// Return a slice containing all elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        ArrayVecImpl::as_slice(self)
+    }
+
+    /// Return a mutable slice containing all elements of the vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        ArrayVecImpl::as_mut_slice(self)
+    }
+
+    /// Return a raw pointer to the vector's buffer.
+    pub fn as_ptr(&self) -> *const T {
+        ArrayVecImpl::as_ptr(self)
+    }
+
+    /// Return a raw mutable pointer to the vector's buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        ArrayVecImpl::as_mut_ptr(self)
+    }
+
+    /// Set the vector’s length without dropping or moving out elements
+    ///
+    /// This method uses *debug assertions* to check that `length` is
+    /// not greater than the capacity.
+    ///
+    /// # Safety
+    ///
+    /// This changes the notion of the number of “valid” elements in the
+    /// vector: the first `length` elements must be initialized.
+    pub unsafe fn set_len(&mut self, length: usize) {
+        ArrayVecImpl::set_len(self, length)
+    }
+}
+
+impl<T, L: LenUint> ArrayVecImpl for ArrayVecView<T, L> {
+    type Item = T;
+
+    fn capacity(&self) -> usize { self.xs.len() }
+
+    fn len(&self) -> usize { len_to_usize(&self.len) }
+
+    unsafe fn set_len(&mut self, length: usize) {
+        debug_assert!(length <= self.capacity());
+        self.len = L::from_usize(length);
+    }
+
+    fn as_ptr(&self) -> *const Self::Item {
+        self.xs.as_ptr() as _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut Self::Item {
+        self.xs.as_mut_ptr() as _
+    }
+}
+
+impl<T, L: LenUint> Deref for ArrayVecView<T, L> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, L: LenUint> DerefMut for ArrayVecView<T, L> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Extend the vector with an iterator.
+///
+/// ***Panics*** if extending the vector exceeds its capacity.
+impl<T, L: LenUint> Extend<T> for ArrayVecView<T, L> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for elt in iter {
+            self.push(elt);
+        }
+    }
+}
+
+impl<T, L: LenUint> PartialEq<[T]> for ArrayVecView<T, L>
+    where T: PartialEq
+{
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T, L: LenUint> fmt::Debug for ArrayVecView<T, L> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+/// A draining iterator for `ArrayVecView`.
+pub struct Drain<'a, T: 'a, L: LenUint = u32> {
+    inner: DrainImpl<'a, ArrayVecView<T, L>>,
+}
+
+unsafe impl<'a, T: Sync, L: LenUint> Sync for Drain<'a, T, L> {}
+unsafe impl<'a, T: Send, L: LenUint> Send for Drain<'a, T, L> {}
+
+impl<'a, T: 'a, L: LenUint> Iterator for Drain<'a, T, L> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T: 'a, L: LenUint> DoubleEndedIterator for Drain<'a, T, L>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T: 'a, L: LenUint> ExactSizeIterator for Drain<'a, T, L> {}
+
+/// A capacity-erased view of an [`ArrayString`](crate::ArrayString).
+///
+/// An `&mut ArrayStringView<L>` is obtained from any `ArrayString<CAP, L>`
+/// with [`as_mut_view`](crate::ArrayString::as_mut_view). The capacity is
+/// only known at runtime, so a function taking a view is not generic over
+/// `CAP`.
+///
+/// ```
+/// use arrayvec::{ArrayString, ArrayStringView};
+///
+/// fn greet(s: &mut ArrayStringView, name: &str) {
+///     s.push_str("Hello, ");
+///     s.push_str(name);
+/// }
+///
+/// let mut string = ArrayString::<16>::new();
+/// greet(string.as_mut_view(), "world");
+/// assert_eq!(&string[..], "Hello, world");
+/// ```
+#[repr(transparent)]
+pub struct ArrayStringView<L: LenUint = u32> {
+    vec: ArrayVecView<u8, L>,
+}
+
+impl<L: LenUint> ArrayStringView<L> {
+    /// Create a view from a pointer to an `ArrayString<CAP, L>` and its
+    /// capacity.
+    ///
+    /// Safety: `ptr` must point to an `ArrayString<cap, L>`, valid for the
+    /// lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw<'a>(ptr: *const u8, cap: usize) -> &'a Self {
+        &*(ArrayVecView::<u8, L>::from_raw(ptr, cap) as *const ArrayVecView<u8, L> as *const Self)
+    }
+
+    /// Create a mutable view from a pointer to an `ArrayString<CAP, L>` and
+    /// its capacity.
+    ///
+    /// Safety: `ptr` must point to an `ArrayString<cap, L>`, valid and
+    /// unaliased for the lifetime `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw_mut<'a>(ptr: *mut u8, cap: usize) -> &'a mut Self {
+        &mut *(ArrayVecView::<u8, L>::from_raw_mut(ptr, cap) as *mut ArrayVecView<u8, L> as *mut Self)
+    }
+
+    /// Return the length of the string.
+    #[inline]
+    pub fn len(&self) -> usize { self.vec.len() }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the underlying string.
+    #[inline]
+    pub fn capacity(&self) -> usize { self.vec.capacity() }
+
+    /// Return if the string is completely filled.
+    pub fn is_full(&self) -> bool { self.vec.is_full() }
+
+    /// Returns the capacity left in the string.
+    pub fn remaining_capacity(&self) -> usize { self.vec.remaining_capacity() }
+
+    /// Adds the given char to the end of the string.
+    ///
+    /// ***Panics*** if the backing array is not large enough to fit the additional char.
+    #[track_caller]
+    pub fn push(&mut self, c: char) {
+        self.try_push(c).unwrap();
+    }
+
+    /// Adds the given char to the end of the string.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the additional char.
+    ///
+    /// ```
+    /// use arrayvec::ArrayString;
+    ///
+    /// let mut string = ArrayString::<2>::new();
+    /// let view = string.as_mut_view();
+    /// view.try_push('a').unwrap();
+    /// assert_eq!(view.try_push('é').unwrap_err().element(), 'é');
+    /// ```
+    pub fn try_push(&mut self, c: char) -> Result<(), CapacityError<char>> {
+        let len = self.len();
+        unsafe {
+            let ptr = self.vec.as_mut_ptr().add(len);
+            let remaining_cap = self.capacity() - len;
+            match encode_utf8(c, ptr, remaining_cap) {
+                Ok(n) => {
+                    self.vec.set_len(len + n);
+                    Ok(())
+                }
+                Err(_) => Err(CapacityError::new(c)),
+            }
+        }
+    }
+
+    /// Adds the given string slice to the end of the string.
+    ///
+    /// ***Panics*** if the backing array is not large enough to fit the string.
+    #[track_caller]
+    pub fn push_str(&mut self, s: &str) {
+        self.try_push_str(s).unwrap()
+    }
+
+    /// Adds the given string slice to the end of the string.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the string.
+    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        self.vec.try_extend_from_slice(s.as_bytes())
+            .map_err(|_| CapacityError::new(s))
+    }
+
+    /// Removes the last character from the string and returns it.
+    ///
+    /// Returns `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        let new_len = self.len() - ch.len_utf8();
+        unsafe {
+            self.vec.set_len(new_len);
+        }
+        Some(ch)
+    }
+
+    /// Shortens the string to the specified length.
+    ///
+    /// If `new_len` is greater than the string’s current length, this has no
+    /// effect.
+    ///
+    /// ***Panics*** if `new_len` does not lie on a `char` boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len <= self.len() {
+            assert!(self.is_char_boundary(new_len));
+            self.vec.truncate(new_len);
+        }
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    /// Removes a `char` from the string at a byte position and returns it.
+    ///
+    /// ***Panics*** if `idx` is larger than or equal to the string’s length,
+    /// or if it does not lie on a `char` boundary.
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = match self[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+        self.vec.drain(idx..idx + ch.len_utf8());
+        ch
+    }
+
+    /// Return a string slice of the whole string.
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Return a mutable string slice of the whole string.
+    pub fn as_mut_str(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl<L: LenUint> Deref for ArrayStringView<L> {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        // SAFETY: the view only holds valid UTF-8
+        unsafe { str::from_utf8_unchecked(&self.vec) }
+    }
+}
+
+impl<L: LenUint> DerefMut for ArrayStringView<L> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        // SAFETY: the view only holds valid UTF-8
+        unsafe { str::from_utf8_unchecked_mut(&mut self.vec) }
+    }
+}
+
+impl<L: LenUint> PartialEq<str> for ArrayStringView<L> {
+    fn eq(&self, rhs: &str) -> bool {
+        &**self == rhs
+    }
+}
+
+impl<L: LenUint> fmt::Debug for ArrayStringView<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<L: LenUint> fmt::Display for ArrayStringView<L> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+/// `Write` appends written data to the end of the string.
+impl<L: LenUint> fmt::Write for ArrayStringView<L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.try_push_str(s).map_err(|_| fmt::Error)
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.try_push(c).map_err(|_| fmt::Error)
+    }
+}
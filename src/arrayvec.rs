@@ -20,32 +20,42 @@ use std::mem::MaybeUninit;
 #[cfg(feature="serde")]
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
+#[cfg(feature="alloc")]
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+
 use crate::LenUint;
+use crate::len_uint::len_to_usize;
 use crate::errors::CapacityError;
-use crate::arrayvec_impl::ArrayVecImpl;
-use crate::utils::MakeMaybeUninit;
+use crate::arrayvec_impl::{ArrayVecImpl, DrainImpl};
+use crate::array_view::ArrayVecView;
+use crate::utils::{CapacityFits, MakeMaybeUninit};
 
 /// A vector with a fixed capacity.
 ///
 /// The `ArrayVec` is a vector backed by a fixed size array. It keeps track of
-/// the number of initialized elements. The `ArrayVec<T, CAP>` is parameterized
-/// by `T` for the element type and `CAP` for the maximum capacity.
+/// the number of initialized elements. The `ArrayVec<T, CAP, L>` is parameterized
+/// by `T` for the element type, `CAP` for the maximum capacity and `L` for the
+/// integer type that stores the length, which defaults to `u32`.
 ///
-/// `CAP` is of type `usize` but is range limited to `u32::MAX`; attempting to create larger
-/// arrayvecs with larger capacity will panic.
+/// `CAP` is of type `usize` but is range limited to [`L::MAX`](LenUint::MAX); attempting to
+/// create larger arrayvecs with larger capacity will panic. A smaller length type such as `u8`
+/// makes the `ArrayVec` smaller.
 ///
 /// The vector is a contiguous value (storing the elements inline) that you can store directly on
 /// the stack if needed.
 ///
 /// It offers a simple API but also dereferences to a slice, so that the full slice API is
 /// available. The ArrayVec can be converted into a by value iterator.
-pub struct ArrayVec<T, const CAP: usize> {
+// `repr(C)` so that the layout matches `ArrayVecCopy<T, CAP, L>` and
+// `ArrayVecView<T, L>`, and for `ArrayVec<u8, CAP, L>` also `ArrayString<CAP, L>`
+#[repr(C)]
+pub struct ArrayVec<T, const CAP: usize, L: LenUint = u32> {
+    len: L,
     // the `len` first elements of the array are initialized
     xs: [MaybeUninit<T>; CAP],
-    len: LenUint,
 }
 
-impl<T, const CAP: usize> Drop for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Drop for ArrayVec<T, CAP, L> {
     fn drop(&mut self) {
         self.clear();
 
@@ -53,17 +63,9 @@ impl<T, const CAP: usize> Drop for ArrayVec<T, CAP> {
     }
 }
 
-macro_rules! panic_oob {
-    ($method_name:expr, $index:expr, $len:expr) => {
-        panic!(concat!("ArrayVec::", $method_name, ": index {} is out of bounds in vector of length {}"),
-               $index, $len)
-    }
-}
-
+// The length type does not take part in inference, so `new` is only for the
+// default one, like `Vec::new` is only for the global allocator.
 impl<T, const CAP: usize> ArrayVec<T, CAP> {
-    /// Capacity
-    const CAPACITY: usize = CAP;
-
     /// Create a new empty `ArrayVec`.
     ///
     /// The maximum capacity is given by the generic parameter `CAP`.
@@ -80,10 +82,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     #[inline]
     #[track_caller]
     pub fn new() -> ArrayVec<T, CAP> {
-        assert_capacity_limit!(CAP);
-        unsafe {
-            ArrayVec { xs: MaybeUninit::uninit().assume_init(), len: 0 }
-        }
+        ArrayVec::new_with_len_type()
     }
 
     /// Create a new empty `ArrayVec` (const fn).
@@ -96,8 +95,47 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// static ARRAY: ArrayVec<u8, 1024> = ArrayVec::new_const();
     /// ```
     pub const fn new_const() -> ArrayVec<T, CAP> {
-        assert_capacity_limit_const!(CAP);
-        ArrayVec { xs: MakeMaybeUninit::ARRAY, len: 0 }
+        ArrayVec::new_with_len_type_const()
+    }
+}
+
+impl<T, const CAP: usize, L: LenUint> ArrayVec<T, CAP, L> {
+    /// Capacity
+    const CAPACITY: usize = CAP;
+
+    /// Create a new empty `ArrayVec` with the length type `L`.
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<u8, 16, u8>::new_with_len_type();
+    /// array.push(1);
+    /// assert_eq!(&array[..], &[1]);
+    /// assert_eq!(std::mem::size_of_val(&array), 17);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn new_with_len_type() -> ArrayVec<T, CAP, L> {
+        assert_capacity_limit!(CAP, L);
+        unsafe {
+            ArrayVec { xs: MaybeUninit::uninit().assume_init(), len: L::ZERO }
+        }
+    }
+
+    /// Create a new empty `ArrayVec` with the length type `L` (const fn).
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// static ARRAY: ArrayVec<u8, 255, u8> = ArrayVec::new_with_len_type_const();
+    /// ```
+    pub const fn new_with_len_type_const() -> ArrayVec<T, CAP, L> {
+        assert_capacity_limit_const!(CAP, L);
+        ArrayVec { xs: MakeMaybeUninit::ARRAY, len: L::ZERO }
     }
 
     /// Return the number of elements in the `ArrayVec`.
@@ -110,7 +148,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(array.len(), 2);
     /// ```
     #[inline(always)]
-    pub const fn len(&self) -> usize { self.len as usize }
+    pub const fn len(&self) -> usize { len_to_usize(&self.len) }
 
     /// Returns whether the `ArrayVec` is empty.
     ///
@@ -254,6 +292,125 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         ArrayVecImpl::clear(self)
     }
 
+    /// Resize the vector in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with clones of `value`. If `new_len` is less, the vector is truncated.
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity. See `try_resize`
+    /// for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 5>::new();
+    /// array.push("hello");
+    /// array.resize(3, "world");
+    /// assert_eq!(&array[..], &["hello", "world", "world"]);
+    /// array.resize(1, "unused");
+    /// assert_eq!(&array[..], &["hello"]);
+    /// ```
+    #[track_caller]
+    pub fn resize(&mut self, new_len: usize, value: T)
+        where T: Clone
+    {
+        self.try_resize(new_len, value).unwrap()
+    }
+
+    /// Resize the vector in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with clones of `value`. If `new_len` is less, the vector is truncated.
+    ///
+    /// Returns an error with `value` if `new_len` is greater than the capacity;
+    /// the vector is unchanged in that case.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 3>::new();
+    /// assert!(array.try_resize(2, 0).is_ok());
+    /// assert_eq!(&array[..], &[0, 0]);
+    /// assert_eq!(array.try_resize(4, 1).unwrap_err().element(), 1);
+    /// assert_eq!(&array[..], &[0, 0]);
+    /// ```
+    pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError<T>>
+        where T: Clone
+    {
+        if new_len > CAP {
+            return Err(CapacityError::new(value));
+        }
+        let len = self.len();
+        if new_len > len {
+            unsafe {
+                // clone for all but the last new element, which takes `value`
+                self.extend_from_iter::<_, false>(iter::repeat_with(|| value.clone()).take(new_len - len - 1));
+                self.push_unchecked(value);
+            }
+        } else {
+            self.truncate(new_len);
+        }
+        Ok(())
+    }
+
+    /// Resize the vector in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with values generated by calling the closure `f`. If `new_len` is less,
+    /// the vector is truncated.
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity. See `try_resize_with`
+    /// for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array: ArrayVec<_, 5> = (1..3).collect();
+    /// let mut next = 3;
+    /// array.resize_with(5, || { next += 1; next - 1 });
+    /// assert_eq!(&array[..], &[1, 2, 3, 4, 5]);
+    /// ```
+    #[track_caller]
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+        where F: FnMut() -> T
+    {
+        self.try_resize_with(new_len, f).unwrap()
+    }
+
+    /// Resize the vector in-place so that its length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended
+    /// with values generated by calling the closure `f`. If `new_len` is less,
+    /// the vector is truncated.
+    ///
+    /// Returns an error if `new_len` is greater than the capacity; the vector
+    /// is unchanged and `f` is not called in that case.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<Vec<i32>, 2>::new();
+    /// assert!(array.try_resize_with(2, Vec::new).is_ok());
+    /// assert!(array.try_resize_with(3, Vec::new).is_err());
+    /// assert_eq!(array.len(), 2);
+    /// ```
+    pub fn try_resize_with<F>(&mut self, new_len: usize, f: F) -> Result<(), CapacityError>
+        where F: FnMut() -> T
+    {
+        if new_len > CAP {
+            return Err(CapacityError::new(()));
+        }
+        let len = self.len();
+        if new_len > len {
+            unsafe {
+                self.extend_from_iter::<_, false>(iter::repeat_with(f).take(new_len - len));
+            }
+        } else {
+            self.truncate(new_len);
+        }
+        Ok(())
+    }
+
 
     /// Get pointer to where element at `index` would be
     unsafe fn get_unchecked_ptr(&mut self, index: usize) -> *mut T {
@@ -307,28 +464,9 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// ```
     pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), CapacityError<T>> {
         if index > self.len() {
-            panic_oob!("try_insert", index, self.len())
+            panic_oob!("ArrayVec", "try_insert", index, self.len())
         }
-        if self.len() == self.capacity() {
-            return Err(CapacityError::new(element));
-        }
-        let len = self.len();
-
-        // follows is just like Vec<T>
-        unsafe { // infallible
-            // The spot to put the new value
-            {
-                let p: *mut _ = self.get_unchecked_ptr(index);
-                // Shift everything over to make space. (Duplicating the
-                // `index`th element into two consecutive places.)
-                ptr::copy(p, p.offset(1), len - index);
-                // Write it in, overwriting the first copy of the `index`th
-                // element.
-                ptr::write(p, element);
-            }
-            self.set_len(len + 1);
-        }
-        Ok(())
+        ArrayVecImpl::try_insert(self, index, element)
     }
 
     /// Remove the last element in the vector and return it.
@@ -371,7 +509,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     pub fn swap_remove(&mut self, index: usize) -> T {
         self.swap_pop(index)
             .unwrap_or_else(|| {
-                panic_oob!("swap_remove", index, self.len())
+                panic_oob!("ArrayVec", "swap_remove", index, self.len())
             })
     }
 
@@ -393,12 +531,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(array.swap_pop(10), None);
     /// ```
     pub fn swap_pop(&mut self, index: usize) -> Option<T> {
-        let len = self.len();
-        if index >= len {
-            return None;
-        }
-        self.swap(index, len - 1);
-        self.pop()
+        ArrayVecImpl::swap_pop(self, index)
     }
 
     /// Remove the element at `index` and shift down the following elements.
@@ -419,7 +552,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     pub fn remove(&mut self, index: usize) -> T {
         self.pop_at(index)
             .unwrap_or_else(|| {
-                panic_oob!("remove", index, self.len())
+                panic_oob!("ArrayVec", "remove", index, self.len())
             })
     }
 
@@ -440,11 +573,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert!(array.pop_at(10).is_none());
     /// ```
     pub fn pop_at(&mut self, index: usize) -> Option<T> {
-        if index >= self.len() {
-            None
-        } else {
-            self.drain(index..index + 1).next()
-        }
+        ArrayVecImpl::pop_at(self, index)
     }
 
     /// Retains only the elements specified by the predicate.
@@ -460,78 +589,172 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// array.retain(|x| *x & 1 != 0 );
     /// assert_eq!(&array[..], &[1, 3]);
     /// ```
-    pub fn retain<F>(&mut self, mut f: F)
+    pub fn retain<F>(&mut self, f: F)
         where F: FnMut(&mut T) -> bool
+    {
+        ArrayVecImpl::retain(self, f)
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// `PartialEq` trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([1, 2, 2, 3, 2]);
+    /// array.dedup();
+    /// assert_eq!(&array[..], &[1, 2, 3, 2]);
+    /// ```
+    pub fn dedup(&mut self)
+        where T: PartialEq
+    {
+        self.dedup_by(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector that
+    /// resolve to the same key.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from([10, 20, 21, 30, 20]);
+    /// array.dedup_by_key(|x| *x / 10);
+    /// assert_eq!(&array[..], &[10, 20, 30, 20]);
+    /// ```
+    pub fn dedup_by_key<F, K>(&mut self, mut key: F)
+        where F: FnMut(&mut T) -> K,
+              K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b))
+    }
+
+    /// Removes all but the first of consecutive elements in the vector
+    /// satisfying a given equality relation.
+    ///
+    /// The `same_bucket` function is passed references to two elements from the
+    /// vector and must determine if the elements compare equal. The elements are
+    /// passed in opposite order from their order in the vector, so if
+    /// `same_bucket(a, b)` returns `true`, `a` is removed.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::from(["foo", "bar", "Bar", "baz", "bar"]);
+    /// array.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(&array[..], &["foo", "bar", "baz", "bar"]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+        where F: FnMut(&mut T, &mut T) -> bool
     {
         // Check the implementation of
-        // https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain
-        // for safety arguments (especially regarding panics in f and when
-        // dropping elements). Implementation closely mirrored here.
+        // https://doc.rust-lang.org/std/vec/struct.Vec.html#method.dedup_by
+        // for safety arguments (especially regarding panics in same_bucket and
+        // when dropping elements). Implementation closely mirrored here.
 
-        let original_len = self.len();
-        unsafe { self.set_len(0) };
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
 
-        struct BackshiftOnDrop<'a, T, const CAP: usize> {
-            v: &'a mut ArrayVec<T, CAP>,
-            processed_len: usize,
-            deleted_cnt: usize,
-            original_len: usize,
+        // Elements `..write` are retained, `write..read` are dropped or moved
+        // out, and `read..` are not yet processed.
+        struct FillGapOnDrop<'a, T, const CAP: usize, L: LenUint> {
+            v: &'a mut ArrayVec<T, CAP, L>,
+            read: usize,
+            write: usize,
         }
 
-        impl<T, const CAP: usize> Drop for BackshiftOnDrop<'_, T, CAP> {
+        impl<T, const CAP: usize, L: LenUint> Drop for FillGapOnDrop<'_, T, CAP, L> {
             fn drop(&mut self) {
-                if self.deleted_cnt > 0 {
-                    unsafe {
-                        ptr::copy(
-                            self.v.as_ptr().add(self.processed_len),
-                            self.v.as_mut_ptr().add(self.processed_len - self.deleted_cnt),
-                            self.original_len - self.processed_len
-                        );
-                    }
-                }
+                // Only reached if `same_bucket` or a destructor panics: move the
+                // unprocessed elements over the gap.
                 unsafe {
-                    self.v.set_len(self.original_len - self.deleted_cnt);
+                    let ptr = self.v.as_mut_ptr();
+                    let len = self.v.len();
+                    ptr::copy(
+                        ptr.add(self.read),
+                        ptr.add(self.write),
+                        len - self.read
+                    );
+                    self.v.set_len(len - (self.read - self.write));
                 }
             }
         }
 
-        let mut g = BackshiftOnDrop { v: self, processed_len: 0, deleted_cnt: 0, original_len };
-
-        #[inline(always)]
-        fn process_one<F: FnMut(&mut T) -> bool, T, const CAP: usize, const DELETED: bool>(
-            f: &mut F,
-            g: &mut BackshiftOnDrop<'_, T, CAP>
-        ) -> bool {
-            let cur = unsafe { g.v.as_mut_ptr().add(g.processed_len) };
-            if !f(unsafe { &mut *cur }) {
-                g.processed_len += 1;
-                g.deleted_cnt += 1;
-                unsafe { ptr::drop_in_place(cur) };
-                return false;
-            }
-            if DELETED {
-                unsafe {
-                    let hole_slot = cur.sub(g.deleted_cnt);
-                    ptr::copy_nonoverlapping(cur, hole_slot, 1);
+        let mut g = FillGapOnDrop { v: self, read: 1, write: 1 };
+        let ptr = g.v.as_mut_ptr();
+
+        unsafe {
+            while g.read < len {
+                let read_ptr = ptr.add(g.read);
+                let prev_ptr = ptr.add(g.write - 1);
+                if same_bucket(&mut *read_ptr, &mut *prev_ptr) {
+                    // Increase `read` first, so the element is not dropped
+                    // again if its destructor panics.
+                    g.read += 1;
+                    ptr::drop_in_place(read_ptr);
+                } else {
+                    let write_ptr = ptr.add(g.write);
+                    // `read_ptr` and `write_ptr` may be equal
+                    ptr::copy(read_ptr, write_ptr, 1);
+                    g.write += 1;
+                    g.read += 1;
                 }
             }
-            g.processed_len += 1;
-            true
-        }
 
-        // Stage 1: Nothing was deleted.
-        while g.processed_len != original_len {
-            if !process_one::<F, T, CAP, false>(&mut f, &mut g) {
-                break;
-            }
+            g.v.set_len(g.write);
+            mem::forget(g);
         }
+    }
 
-        // Stage 2: Some elements were deleted.
-        while g.processed_len != original_len {
-            process_one::<F, T, CAP, true>(&mut f, &mut g);
-        }
+    /// Create an iterator which uses a closure to determine if an element in the
+    /// range should be removed.
+    ///
+    /// If the closure returns true, the element is removed from the vector and
+    /// yielded by value. If the closure returns false, the element remains in the
+    /// vector. The retained elements are compacted in place and keep their order.
+    ///
+    /// If the `ExtractIf` is dropped before it is exhausted, the remaining elements
+    /// are retained; if the closure panics, the element it was called on and all
+    /// following elements are retained.
+    ///
+    /// **Panics** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut numbers = ArrayVec::from([1, 2, 3, 4, 5, 6, 8, 9, 11, 13, 14, 15]);
+    ///
+    /// let evens: ArrayVec<_, 12> = numbers.extract_if(.., |x| *x % 2 == 0).collect();
+    ///
+    /// assert_eq!(&evens[..], &[2, 4, 6, 8, 14]);
+    /// assert_eq!(&numbers[..], &[1, 3, 5, 9, 11, 13, 15]);
+    /// ```
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F, CAP, L>
+        where F: FnMut(&mut T) -> bool,
+              R: RangeBounds<usize>,
+    {
+        let old_len = self.len();
+        let (start, end) = resolve_range(range, old_len);
+        // bounds check happens here (before length is changed!)
+        let _ = &self[start..end];
+
+        // Guard against the vector getting leaked (leak amplification)
+        unsafe { self.set_len(0) };
 
-        drop(g);
+        ExtractIf {
+            vec: self,
+            idx: start,
+            end,
+            del: 0,
+            old_len,
+            pred: filter,
+        }
     }
 
     /// Set the vector’s length without dropping or moving out elements
@@ -544,7 +767,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     pub unsafe fn set_len(&mut self, length: usize) {
         // type invariant that capacity always fits in LenUint
         debug_assert!(length <= self.capacity());
-        self.len = length as LenUint;
+        self.len = L::from_usize(length);
     }
 
     /// Copy all elements from the slice and append to the `ArrayVec`.
@@ -568,21 +791,201 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
         where T: Copy,
     {
-        if self.remaining_capacity() < other.len() {
-            return Err(CapacityError::new(()));
+        ArrayVecImpl::try_extend_from_slice(self, other)
+    }
+
+    /// Extend the `ArrayVec` with the elements of the iterator, until it is
+    /// full.
+    ///
+    /// **Errors** if the iterator has more elements than fit, with the first
+    /// element that did not fit and the rest of the iterator. The vector is
+    /// full in that case.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut array = ArrayVec::<_, 4>::new();
+    /// array.try_extend(0..2).unwrap();
+    ///
+    /// let (overflow, rest) = array.try_extend(2..7).unwrap_err();
+    /// assert_eq!(&array[..], &[0, 1, 2, 3]);
+    /// assert_eq!(overflow, 4);
+    /// assert_eq!(rest.collect::<Vec<_>>(), [5, 6]);
+    /// ```
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), (T, I::IntoIter)>
+        where I: IntoIterator<Item = T>
+    {
+        let mut iter = iter.into_iter();
+        let take = self.remaining_capacity();
+        unsafe {
+            self.extend_from_iter::<_, false>(iter.by_ref().take(take));
         }
+        match iter.next() {
+            None => Ok(()),
+            Some(elt) => Err((elt, iter)),
+        }
+    }
 
+    /// Create a new `ArrayVec` from the elements of the iterator.
+    ///
+    /// **Errors** if the iterator has more elements than fit, with the full
+    /// vector, the first element that did not fit and the rest of the
+    /// iterator. This splits an iterator into bounded batches without losing
+    /// any elements:
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut batches = Vec::new();
+    /// let mut iter = 0..7;
+    /// loop {
+    ///     match ArrayVec::<_, 3>::try_from_iter(iter) {
+    ///         Ok(last) => {
+    ///             batches.push(last);
+    ///             break;
+    ///         }
+    ///         Err((batch, next, rest)) => {
+    ///             batches.push(batch);
+    ///             iter = next..rest.end;
+    ///         }
+    ///     }
+    /// }
+    /// let batches: Vec<&[i32]> = batches.iter().map(|b| &b[..]).collect();
+    /// assert_eq!(batches, [&[0, 1, 2][..], &[3, 4, 5], &[6]]);
+    /// ```
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, (Self, T, I::IntoIter)>
+        where I: IntoIterator<Item = T>
+    {
+        let mut array = Self::new_with_len_type();
+        match array.try_extend(iter) {
+            Ok(()) => Ok(array),
+            Err((elt, rest)) => Err((array, elt, rest)),
+        }
+    }
+
+    /// Move all the elements of `other` to the end of `self`, leaving `other` empty.
+    ///
+    /// The two vectors may have different capacities.
+    ///
+    /// ***Panics*** if the elements of `other` do not fit in the remaining capacity
+    /// of `self`. See `try_append` for fallible version.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::<_, 5>::new();
+    /// v1.push(1);
+    /// let mut v2 = ArrayVec::from([2, 3, 4]);
+    /// v1.append(&mut v2);
+    /// assert_eq!(&v1[..], &[1, 2, 3, 4]);
+    /// assert!(v2.is_empty());
+    /// ```
+    #[track_caller]
+    pub fn append<const OTHER_CAP: usize, L2: LenUint>(&mut self, other: &mut ArrayVec<T, OTHER_CAP, L2>) {
+        self.try_append(other).unwrap()
+    }
+
+    /// Move all the elements of `other` to the end of `self`, leaving `other` empty.
+    ///
+    /// The two vectors may have different capacities.
+    ///
+    /// Returns an error if the elements of `other` do not fit in the remaining
+    /// capacity of `self`; both vectors are unchanged in that case.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::<_, 3>::new();
+    /// let mut v2 = ArrayVec::from([1, 2]);
+    /// assert!(v1.try_append(&mut v2).is_ok());
+    ///
+    /// let mut v3 = ArrayVec::from([3, 4]);
+    /// assert!(v1.try_append(&mut v3).is_err());
+    /// assert_eq!(&v1[..], &[1, 2]);
+    /// assert_eq!(&v3[..], &[3, 4]);
+    /// ```
+    pub fn try_append<const OTHER_CAP: usize, L2: LenUint>(&mut self, other: &mut ArrayVec<T, OTHER_CAP, L2>)
+        -> Result<(), CapacityError>
+    {
         let self_len = self.len();
         let other_len = other.len();
+        if self.remaining_capacity() < other_len {
+            return Err(CapacityError::new(()));
+        }
 
         unsafe {
             let dst = self.get_unchecked_ptr(self_len);
             ptr::copy_nonoverlapping(other.as_ptr(), dst, other_len);
+            other.set_len(0);
             self.set_len(self_len + other_len);
         }
         Ok(())
     }
 
+    /// Split the vector into two at the given index.
+    ///
+    /// Return a new vector containing the elements in the range `[at, len)`;
+    /// `self` is left containing the elements `[0, at)`. The returned vector
+    /// may have a different capacity, `CAP2`.
+    ///
+    /// ***Panics*** if `at` is greater than the length, or if the split off
+    /// elements do not fit in `CAP2`. See `try_split_off` for a version that
+    /// does not panic on capacity overflow.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::from([1, 2, 3, 4]);
+    /// let v2: ArrayVec<_, 2> = v1.split_off(2);
+    /// assert_eq!(&v1[..], &[1, 2]);
+    /// assert_eq!(&v2[..], &[3, 4]);
+    /// ```
+    #[track_caller]
+    pub fn split_off<const CAP2: usize>(&mut self, at: usize) -> ArrayVec<T, CAP2, L> {
+        self.try_split_off(at).unwrap()
+    }
+
+    /// Split the vector into two at the given index.
+    ///
+    /// Return a new vector containing the elements in the range `[at, len)`;
+    /// `self` is left containing the elements `[0, at)`. The returned vector
+    /// may have a different capacity, `CAP2`.
+    ///
+    /// Returns an error if the split off elements do not fit in `CAP2`; the
+    /// vector is unchanged in that case.
+    ///
+    /// ***Panics*** if `at` is greater than the length.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v1 = ArrayVec::from([1, 2, 3, 4]);
+    /// assert!(v1.try_split_off::<2>(1).is_err());
+    /// let v2 = v1.try_split_off::<3>(1).unwrap();
+    /// assert_eq!(&v1[..], &[1]);
+    /// assert_eq!(&v2[..], &[2, 3, 4]);
+    /// ```
+    pub fn try_split_off<const CAP2: usize>(&mut self, at: usize)
+        -> Result<ArrayVec<T, CAP2, L>, CapacityError>
+    {
+        let len = self.len();
+        if at > len {
+            panic_oob!("ArrayVec", "try_split_off", at, len)
+        }
+        let other_len = len - at;
+        if other_len > CAP2 {
+            return Err(CapacityError::new(()));
+        }
+
+        let mut other = ArrayVec::new_with_len_type();
+        unsafe {
+            self.set_len(at);
+            ptr::copy_nonoverlapping(self.get_unchecked_ptr(at), other.as_mut_ptr(), other_len);
+            other.set_len(other_len);
+        }
+        Ok(other)
+    }
+
     /// Create a draining iterator that removes the specified range in the vector
     /// and yields the removed items from start to end. The element range is
     /// removed even if the iterator is not consumed until the end.
@@ -601,52 +1004,91 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert_eq!(&v1[..], &[3]);
     /// assert_eq!(&v2[..], &[1, 2]);
     /// ```
-    pub fn drain<R>(&mut self, range: R) -> Drain<T, CAP>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP, L>
         where R: RangeBounds<usize>
     {
-        // Memory safety
-        //
-        // When the Drain is first created, it shortens the length of
-        // the source vector to make sure no uninitialized or moved-from elements
-        // are accessible at all if the Drain's destructor never gets to run.
-        //
-        // Drain will ptr::read out the values to remove.
-        // When finished, remaining tail of the vec is copied back to cover
-        // the hole, and the vector length is restored to the new length.
-        //
-        let len = self.len();
-        let start = match range.start_bound() {
-            Bound::Unbounded => 0,
-            Bound::Included(&i) => i,
-            Bound::Excluded(&i) => i.saturating_add(1),
-        };
-        let end = match range.end_bound() {
-            Bound::Excluded(&j) => j,
-            Bound::Included(&j) => j.saturating_add(1),
-            Bound::Unbounded => len,
-        };
+        let (start, end) = resolve_range(range, self.len());
         self.drain_range(start, end)
     }
 
-    fn drain_range(&mut self, start: usize, end: usize) -> Drain<T, CAP>
+    fn drain_range(&mut self, start: usize, end: usize) -> Drain<'_, T, CAP, L>
     {
-        let len = self.len();
-
-        // bounds check happens here (before length is changed!)
-        let range_slice: *const _ = &self[start..end];
+        Drain { inner: ArrayVecImpl::drain_range(self, start, end) }
+    }
 
-        // Calling `set_len` creates a fresh and thus unique mutable references, making all
-        // older aliases we created invalid. So we cannot call that function.
-        self.len = start as LenUint;
+    /// Create a splicing iterator that replaces the specified range in the vector
+    /// with the given `replace_with` iterator and yields the removed items.
+    /// `replace_with` does not need to be the same length as `range`.
+    ///
+    /// The element range is removed even if the iterator is not consumed until the end.
+    /// The replacement happens when the `Splice` value is dropped; `replace_with`
+    /// is only consumed at that point.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point, if the end
+    /// point is greater than the length of the vector, or (when the `Splice` is
+    /// dropped) if the replacement does not fit in the capacity. See `try_splice`
+    /// for a version that checks the capacity up front.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v: ArrayVec<_, 5> = (1..5).collect();
+    /// let removed: Vec<_> = v.splice(1..3, [7, 8, 9]).collect();
+    /// assert_eq!(&v[..], &[1, 7, 8, 9, 4]);
+    /// assert_eq!(removed, [2, 3]);
+    /// ```
+    #[track_caller]
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, CAP, L>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
 
-        unsafe {
-            Drain {
-                tail_start: end,
-                tail_len: len - end,
-                iter: (*range_slice).iter(),
-                vec: self as *mut _,
-            }
+    /// Create a splicing iterator like `splice`, if the replacement fits in the
+    /// capacity of the vector.
+    ///
+    /// The final length is computed from the exact length of `replace_with`
+    /// before the vector is modified. If it would exceed the capacity, the vector
+    /// is left untouched and the unconsumed `replace_with` iterator is returned
+    /// in the error.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v = ArrayVec::from([1, 2, 3]);
+    ///
+    /// let removed: Vec<_> = v.try_splice(..1, [0]).unwrap().collect();
+    /// assert_eq!(removed, [1]);
+    /// assert_eq!(&v[..], &[0, 2, 3]);
+    ///
+    /// let overflow = v.try_splice(1..2, [4, 5]);
+    /// assert_eq!(overflow.err().unwrap().element().collect::<Vec<_>>(), [4, 5]);
+    /// assert_eq!(&v[..], &[0, 2, 3]);
+    /// ```
+    pub fn try_splice<R, I>(&mut self, range: R, replace_with: I)
+        -> Result<Splice<'_, I::IntoIter, CAP, L>, CapacityError<I::IntoIter>>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        let (start, end) = resolve_range(range, self.len());
+        // bounds check happens here, before the capacity check
+        let removed = self[start..end].len();
+        let replace_with = replace_with.into_iter();
+        if replace_with.len() > self.remaining_capacity() + removed {
+            return Err(CapacityError::new(replace_with));
         }
+        Ok(Splice {
+            drain: self.drain_range(start, end),
+            replace_with,
+        })
     }
 
     /// Return the inner fixed size array, if it is full to its capacity.
@@ -672,6 +1114,70 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
         array
     }
 
+    /// Convert into an `ArrayVec` with capacity `CAP2`, moving all elements.
+    ///
+    /// The new capacity must be at least as large as the current capacity;
+    /// this is checked at compile time.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let v = ArrayVec::from([1, 2, 3]);
+    /// let mut w: ArrayVec<_, 8> = v.into_capacity();
+    /// w.push(4);
+    /// assert_eq!(&w[..], &[1, 2, 3, 4]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let v = ArrayVec::<i32, 8>::new();
+    /// let w: ArrayVec<_, 4> = v.into_capacity();
+    /// ```
+    pub fn into_capacity<const CAP2: usize>(self) -> ArrayVec<T, CAP2, L> {
+        #[allow(clippy::let_unit_value)]
+        let () = CapacityFits::<CAP, CAP2>::ASSERT;
+        unsafe { self.into_capacity_unchecked() }
+    }
+
+    /// Convert into an `ArrayVec` with capacity `CAP2`, moving all elements,
+    /// if the elements fit.
+    ///
+    /// Return an `Ok` value with the new vector if the length is at most `CAP2`,
+    /// return an `Err` with self otherwise.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVec;
+    ///
+    /// let mut v = ArrayVec::<_, 16>::new();
+    /// v.extend(0..4);
+    /// let w = v.try_into_capacity::<4>().unwrap();
+    /// assert_eq!(&w[..], &[0, 1, 2, 3]);
+    ///
+    /// let v = w.try_into_capacity::<2>().unwrap_err();
+    /// assert_eq!(&v[..], &[0, 1, 2, 3]);
+    /// ```
+    pub fn try_into_capacity<const CAP2: usize>(self) -> Result<ArrayVec<T, CAP2, L>, Self> {
+        if self.len() > CAP2 {
+            Err(self)
+        } else {
+            unsafe { Ok(self.into_capacity_unchecked()) }
+        }
+    }
+
+    /// Move all elements into a new `ArrayVec` with capacity `CAP2`.
+    ///
+    /// Safety: the length must be at most `CAP2`.
+    unsafe fn into_capacity_unchecked<const CAP2: usize>(mut self) -> ArrayVec<T, CAP2, L> {
+        let len = self.len();
+        debug_assert!(len <= CAP2);
+        let mut other = ArrayVec::new_with_len_type();
+        ptr::copy_nonoverlapping(self.as_ptr(), other.as_mut_ptr(), len);
+        self.set_len(0);
+        other.set_len(len);
+        other
+    }
+
     /// Returns the ArrayVec, replacing the original with a new empty ArrayVec.
     ///
     /// ```
@@ -682,7 +1188,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     /// assert!(v.is_empty());
     /// ```
     pub fn take(&mut self) -> Self  {
-        mem::replace(self, Self::new())
+        mem::replace(self, Self::new_with_len_type())
     }
 
     /// Return a slice containing all elements of the vector.
@@ -704,17 +1210,59 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
     pub fn as_mut_ptr(&mut self) -> *mut T {
         ArrayVecImpl::as_mut_ptr(self)
     }
+
+    /// Return a capacity-erased view of the vector.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayVec, ArrayVecView};
+    ///
+    /// fn describe(v: &ArrayVecView<i32>) -> (usize, usize) {
+    ///     (v.len(), v.capacity())
+    /// }
+    ///
+    /// let mut array = ArrayVec::<i32, 4>::new();
+    /// array.push(1);
+    /// array.push(2);
+    /// assert_eq!(describe(array.as_view()), (2, 4));
+    /// ```
+    pub fn as_view(&self) -> &ArrayVecView<T, L> {
+        // SAFETY: `ArrayVecView<T, L>` has the layout of `ArrayVec<T, CAP, L>`
+        unsafe { ArrayVecView::from_raw(self as *const Self as *const u8, CAP) }
+    }
+
+    /// Return a mutable capacity-erased view of the vector.
+    ///
+    /// A function taking `&mut ArrayVecView<T>` can modify vectors of any
+    /// capacity without being generic over it.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayVec, ArrayVecView};
+    ///
+    /// fn push_twice(v: &mut ArrayVecView<i32>, x: i32) {
+    ///     v.push(x);
+    ///     v.push(x);
+    /// }
+    ///
+    /// let mut array = ArrayVec::<i32, 4>::new();
+    /// push_twice(array.as_mut_view(), 7);
+    /// assert_eq!(&array[..], &[7, 7]);
+    /// ```
+    pub fn as_mut_view(&mut self) -> &mut ArrayVecView<T, L> {
+        // SAFETY: `ArrayVecView<T, L>` has the layout of `ArrayVec<T, CAP, L>`
+        unsafe { ArrayVecView::from_raw_mut(self as *mut Self as *mut u8, CAP) }
+    }
 }
 
-impl<T, const CAP: usize> ArrayVecImpl for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> ArrayVecImpl for ArrayVec<T, CAP, L> {
     type Item = T;
-    const CAPACITY: usize = CAP;
+
+    fn capacity(&self) -> usize { CAP }
 
     fn len(&self) -> usize { self.len() }
 
     unsafe fn set_len(&mut self, length: usize) {
         debug_assert!(length <= CAP);
-        self.len = length as LenUint;
+        self.len = L::from_usize(length);
     }
 
     fn as_ptr(&self) -> *const Self::Item {
@@ -726,7 +1274,7 @@ impl<T, const CAP: usize> ArrayVecImpl for ArrayVec<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> Deref for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Deref for ArrayVec<T, CAP, L> {
     type Target = [T];
     #[inline]
     fn deref(&self) -> &Self::Target {
@@ -734,7 +1282,7 @@ impl<T, const CAP: usize> Deref for ArrayVec<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> DerefMut for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> DerefMut for ArrayVec<T, CAP, L> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut_slice()
@@ -744,6 +1292,8 @@ impl<T, const CAP: usize> DerefMut for ArrayVec<T, CAP> {
 
 /// Create an `ArrayVec` from an array.
 ///
+/// The capacity is inferred from the array, so this uses the default length type.
+///
 /// ```
 /// use arrayvec::ArrayVec;
 ///
@@ -777,7 +1327,7 @@ impl<T, const CAP: usize> From<[T; CAP]> for ArrayVec<T, CAP> {
 /// assert_eq!(array.len(), 3);
 /// assert_eq!(array.capacity(), 4);
 /// ```
-impl<T, const CAP: usize> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenUint> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP, L>
     where T: Clone,
 {
     type Error = CapacityError;
@@ -786,7 +1336,7 @@ impl<T, const CAP: usize> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP>
         if Self::CAPACITY < slice.len() {
             Err(CapacityError::new(()))
         } else {
-            let mut array = Self::new();
+            let mut array = Self::new_with_len_type();
             array.extend_from_slice(slice);
             Ok(array)
         }
@@ -805,7 +1355,7 @@ impl<T, const CAP: usize> std::convert::TryFrom<&[T]> for ArrayVec<T, CAP>
 ///     // ...
 /// }
 /// ```
-impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayVec<T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenUint> IntoIterator for &'a ArrayVec<T, CAP, L> {
     type Item = &'a T;
     type IntoIter = slice::Iter<'a, T>;
     fn into_iter(self) -> Self::IntoIter { self.iter() }
@@ -822,7 +1372,7 @@ impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a ArrayVec<T, CAP> {
 ///     // ...
 /// }
 /// ```
-impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayVec<T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenUint> IntoIterator for &'a mut ArrayVec<T, CAP, L> {
     type Item = &'a mut T;
     type IntoIter = slice::IterMut<'a, T>;
     fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
@@ -839,10 +1389,10 @@ impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut ArrayVec<T, CAP> {
 ///     // ...
 /// }
 /// ```
-impl<T, const CAP: usize> IntoIterator for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> IntoIterator for ArrayVec<T, CAP, L> {
     type Item = T;
-    type IntoIter = IntoIter<T, CAP>;
-    fn into_iter(self) -> IntoIter<T, CAP> {
+    type IntoIter = IntoIter<T, CAP, L>;
+    fn into_iter(self) -> IntoIter<T, CAP, L> {
         IntoIter { index: 0, v: self, }
     }
 }
@@ -863,7 +1413,7 @@ impl<T, const CAP: usize> IntoIterator for ArrayVec<T, CAP> {
 /// let data = unsafe { core::slice::from_raw_parts(array.as_ptr(), array.capacity()) };
 /// assert_eq!(data, [0, 0, 0]);
 /// ```
-impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayVec<Z, CAP> {
+impl<Z: zeroize::Zeroize, const CAP: usize, L: LenUint> zeroize::Zeroize for ArrayVec<Z, CAP, L> {
     fn zeroize(&mut self) {
         // Zeroize all the contained elements.
         self.iter_mut().zeroize();
@@ -875,12 +1425,12 @@ impl<Z: zeroize::Zeroize, const CAP: usize> zeroize::Zeroize for ArrayVec<Z, CAP
 }
 
 /// By-value iterator for `ArrayVec`.
-pub struct IntoIter<T, const CAP: usize> {
+pub struct IntoIter<T, const CAP: usize, L: LenUint = u32> {
     index: usize,
-    v: ArrayVec<T, CAP>,
+    v: ArrayVec<T, CAP, L>,
 }
 
-impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Iterator for IntoIter<T, CAP, L> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -901,7 +1451,7 @@ impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> DoubleEndedIterator for IntoIter<T, CAP, L> {
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.index == self.v.len() {
             None
@@ -915,9 +1465,9 @@ impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> { }
+impl<T, const CAP: usize, L: LenUint> ExactSizeIterator for IntoIter<T, CAP, L> { }
 
-impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Drop for IntoIter<T, CAP, L> {
     fn drop(&mut self) {
         // panic safety: Set length to 0 before dropping elements.
         let index = self.index;
@@ -932,17 +1482,17 @@ impl<T, const CAP: usize> Drop for IntoIter<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> Clone for IntoIter<T, CAP>
+impl<T, const CAP: usize, L: LenUint> Clone for IntoIter<T, CAP, L>
 where T: Clone,
 {
-    fn clone(&self) -> IntoIter<T, CAP> {
-        let mut v = ArrayVec::new();
+    fn clone(&self) -> IntoIter<T, CAP, L> {
+        let mut v = ArrayVec::new_with_len_type();
         v.extend_from_slice(&self.v[self.index..]);
         v.into_iter()
     }
 }
 
-impl<T, const CAP: usize> fmt::Debug for IntoIter<T, CAP>
+impl<T, const CAP: usize, L: LenUint> fmt::Debug for IntoIter<T, CAP, L>
 where
     T: fmt::Debug,
 {
@@ -954,69 +1504,178 @@ where
 }
 
 /// A draining iterator for `ArrayVec`.
-pub struct Drain<'a, T: 'a, const CAP: usize> {
-    /// Index of tail to preserve
-    tail_start: usize,
-    /// Length of tail
-    tail_len: usize,
-    /// Current remaining range to remove
-    iter: slice::Iter<'a, T>,
-    vec: *mut ArrayVec<T, CAP>,
+pub struct Drain<'a, T: 'a, const CAP: usize, L: LenUint = u32> {
+    inner: DrainImpl<'a, ArrayVec<T, CAP, L>>,
 }
 
-unsafe impl<'a, T: Sync, const CAP: usize> Sync for Drain<'a, T, CAP> {}
-unsafe impl<'a, T: Send, const CAP: usize> Send for Drain<'a, T, CAP> {}
+unsafe impl<'a, T: Sync, const CAP: usize, L: LenUint> Sync for Drain<'a, T, CAP, L> {}
+unsafe impl<'a, T: Send, const CAP: usize, L: LenUint> Send for Drain<'a, T, CAP, L> {}
 
-impl<'a, T: 'a, const CAP: usize> Iterator for Drain<'a, T, CAP> {
+impl<'a, T: 'a, const CAP: usize, L: LenUint> Iterator for Drain<'a, T, CAP, L> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|elt|
-            unsafe {
-                ptr::read(elt as *const _)
-            }
-        )
+        self.inner.next()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        self.inner.size_hint()
     }
 }
 
-impl<'a, T: 'a, const CAP: usize> DoubleEndedIterator for Drain<'a, T, CAP>
+impl<'a, T: 'a, const CAP: usize, L: LenUint> DoubleEndedIterator for Drain<'a, T, CAP, L>
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        self.iter.next_back().map(|elt|
-            unsafe {
-                ptr::read(elt as *const _)
-            }
-        )
+        self.inner.next_back()
     }
 }
 
-impl<'a, T: 'a, const CAP: usize> ExactSizeIterator for Drain<'a, T, CAP> {}
+impl<'a, T: 'a, const CAP: usize, L: LenUint> ExactSizeIterator for Drain<'a, T, CAP, L> {}
+/// An iterator which uses a closure to determine if an element should be removed.
+///
+/// See [`ArrayVec::extract_if`].
+pub struct ExtractIf<'a, T, F, const CAP: usize, L: LenUint = u32>
+    where F: FnMut(&mut T) -> bool,
+{
+    vec: &'a mut ArrayVec<T, CAP, L>,
+    /// Index of the next element to inspect
+    idx: usize,
+    /// End of the range to inspect
+    end: usize,
+    /// Number of elements removed so far
+    del: usize,
+    /// Length of the vector before `extract_if` was called
+    old_len: usize,
+    pred: F,
+}
+
+impl<'a, T, F, const CAP: usize, L: LenUint> Iterator for ExtractIf<'a, T, F, CAP, L>
+    where F: FnMut(&mut T) -> bool,
+{
+    type Item = T;
 
-impl<'a, T: 'a, const CAP: usize> Drop for Drain<'a, T, CAP> {
+    fn next(&mut self) -> Option<T> {
+        unsafe {
+            while self.idx < self.end {
+                let i = self.idx;
+                let cur = self.vec.as_mut_ptr().add(i);
+                let extract = (self.pred)(&mut *cur);
+                // Update the index *after* the predicate is called. If the index
+                // is updated prior and the predicate panics, the element at this
+                // index would be leaked.
+                self.idx += 1;
+                if extract {
+                    self.del += 1;
+                    return Some(ptr::read(cur));
+                } else if self.del > 0 {
+                    ptr::copy_nonoverlapping(cur, cur.sub(self.del), 1);
+                }
+            }
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.idx))
+    }
+}
+
+impl<'a, T, F, const CAP: usize, L: LenUint> Drop for ExtractIf<'a, T, F, CAP, L>
+    where F: FnMut(&mut T) -> bool,
+{
     fn drop(&mut self) {
-        // len is currently 0 so panicking while dropping will not cause a double drop.
+        unsafe {
+            if self.idx < self.old_len && self.del > 0 {
+                // backshift the unprocessed elements over the hole
+                let ptr = self.vec.as_mut_ptr();
+                let src = ptr.add(self.idx);
+                let dst = src.sub(self.del);
+                ptr::copy(src, dst, self.old_len - self.idx);
+            }
+            self.vec.set_len(self.old_len - self.del);
+        }
+    }
+}
 
-        // exhaust self first
-        while let Some(_) = self.next() { }
+/// A splicing iterator for `ArrayVec`.
+///
+/// Yields the removed elements; the replacement is inserted when it is dropped.
+/// See [`ArrayVec::splice`] and [`ArrayVec::try_splice`].
+pub struct Splice<'a, I: Iterator + 'a, const CAP: usize, L: LenUint = u32> {
+    drain: Drain<'a, I::Item, CAP, L>,
+    replace_with: I,
+}
 
-        if self.tail_len > 0 {
-            unsafe {
-                let source_vec = &mut *self.vec;
-                // memmove back untouched tail, update to new length
-                let start = source_vec.len();
-                let tail = self.tail_start;
-                let ptr = source_vec.as_mut_ptr();
-                ptr::copy(ptr.add(tail), ptr.add(start), self.tail_len);
-                source_vec.set_len(start + self.tail_len);
+impl<'a, I: Iterator, const CAP: usize, L: LenUint> Iterator for Splice<'a, I, CAP, L> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenUint> DoubleEndedIterator for Splice<'a, I, CAP, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenUint> ExactSizeIterator for Splice<'a, I, CAP, L> {}
+
+impl<'a, I: Iterator, const CAP: usize, L: LenUint> Drop for Splice<'a, I, CAP, L> {
+    #[track_caller]
+    fn drop(&mut self) {
+        // Remove the range first; afterwards the `Drain` restores the tail
+        // when it is dropped, even if we panic below.
+        self.drain.by_ref().for_each(drop);
+
+        let drain = &mut self.drain.inner;
+        unsafe {
+            // The common case: the replacement fits in the drained range.
+            if !drain.fill(&mut self.replace_with) {
+                return;
+            }
+            let next = match self.replace_with.next() {
+                Some(elt) => elt,
+                None => return,
+            };
+            // Move the tail to the end of the buffer to make as much room as
+            // possible, then fill again.
+            drain.move_tail(CAP - drain.tail_len);
+            let mut rest = iter::once(next).chain(&mut self.replace_with);
+            if drain.fill(&mut rest) && rest.next().is_some() {
+                splice_panic();
             }
         }
     }
 }
 
+#[inline(never)]
+#[cold]
+#[track_caller]
+fn splice_panic() {
+    panic!("ArrayVec: capacity exceeded in splice");
+}
+
+/// Resolve `range` into start and end indices for a vector of length `len`.
+pub(crate) fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Unbounded => 0,
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.saturating_add(1),
+    };
+    let end = match range.end_bound() {
+        Bound::Excluded(&j) => j,
+        Bound::Included(&j) => j.saturating_add(1),
+        Bound::Unbounded => len,
+    };
+    (start, end)
+}
+
 struct ScopeExitGuard<T, Data, F>
     where F: FnMut(&Data, &mut T)
 {
@@ -1038,7 +1697,7 @@ impl<T, Data, F> Drop for ScopeExitGuard<T, Data, F>
 /// Extend the `ArrayVec` with an iterator.
 /// 
 /// ***Panics*** if extending the vector exceeds its capacity.
-impl<T, const CAP: usize> Extend<T> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Extend<T> for ArrayVec<T, CAP, L> {
     /// Extend the `ArrayVec` with an iterator.
     /// 
     /// ***Panics*** if extending the vector exceeds its capacity.
@@ -1057,7 +1716,7 @@ fn extend_panic() {
     panic!("ArrayVec: capacity exceeded in extend/from_iter");
 }
 
-impl<T, const CAP: usize> ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> ArrayVec<T, CAP, L> {
     /// Extend the arrayvec from the iterable.
     ///
     /// ## Safety
@@ -1080,7 +1739,7 @@ impl<T, const CAP: usize> ArrayVec<T, CAP> {
             value: &mut self.len,
             data: len,
             f: move |&len, self_len| {
-                **self_len = len as LenUint;
+                **self_len = L::from_usize(len);
             }
         };
         let mut iter = iterable.into_iter();
@@ -1124,18 +1783,18 @@ unsafe fn raw_ptr_add<T>(ptr: *mut T, offset: usize) -> *mut T {
 /// Create an `ArrayVec` from an iterator.
 /// 
 /// ***Panics*** if the number of elements in the iterator exceeds the arrayvec's capacity.
-impl<T, const CAP: usize> iter::FromIterator<T> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> iter::FromIterator<T> for ArrayVec<T, CAP, L> {
     /// Create an `ArrayVec` from an iterator.
     /// 
     /// ***Panics*** if the number of elements in the iterator exceeds the arrayvec's capacity.
     fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
-        let mut array = ArrayVec::new();
+        let mut array = ArrayVec::new_with_len_type();
         array.extend(iter);
         array
     }
 }
 
-impl<T, const CAP: usize> Clone for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenUint> Clone for ArrayVec<T, CAP, L>
     where T: Clone
 {
     fn clone(&self) -> Self {
@@ -1157,7 +1816,7 @@ impl<T, const CAP: usize> Clone for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> Hash for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenUint> Hash for ArrayVec<T, CAP, L>
     where T: Hash
 {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -1165,7 +1824,7 @@ impl<T, const CAP: usize> Hash for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> PartialEq for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenUint> PartialEq for ArrayVec<T, CAP, L>
     where T: PartialEq
 {
     fn eq(&self, other: &Self) -> bool {
@@ -1173,7 +1832,7 @@ impl<T, const CAP: usize> PartialEq for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> PartialEq<[T]> for ArrayVec<T, CAP>
+impl<T, const CAP: usize, L: LenUint> PartialEq<[T]> for ArrayVec<T, CAP, L>
     where T: PartialEq
 {
     fn eq(&self, other: &[T]) -> bool {
@@ -1181,25 +1840,25 @@ impl<T, const CAP: usize> PartialEq<[T]> for ArrayVec<T, CAP>
     }
 }
 
-impl<T, const CAP: usize> Eq for ArrayVec<T, CAP> where T: Eq { }
+impl<T, const CAP: usize, L: LenUint> Eq for ArrayVec<T, CAP, L> where T: Eq { }
 
-impl<T, const CAP: usize> Borrow<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> Borrow<[T]> for ArrayVec<T, CAP, L> {
     fn borrow(&self) -> &[T] { self }
 }
 
-impl<T, const CAP: usize> BorrowMut<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> BorrowMut<[T]> for ArrayVec<T, CAP, L> {
     fn borrow_mut(&mut self) -> &mut [T] { self }
 }
 
-impl<T, const CAP: usize> AsRef<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> AsRef<[T]> for ArrayVec<T, CAP, L> {
     fn as_ref(&self) -> &[T] { self }
 }
 
-impl<T, const CAP: usize> AsMut<[T]> for ArrayVec<T, CAP> {
+impl<T, const CAP: usize, L: LenUint> AsMut<[T]> for ArrayVec<T, CAP, L> {
     fn as_mut(&mut self) -> &mut [T] { self }
 }
 
-impl<T, const CAP: usize> fmt::Debug for ArrayVec<T, CAP> where T: fmt::Debug {
+impl<T, const CAP: usize, L: LenUint> fmt::Debug for ArrayVec<T, CAP, L> where T: fmt::Debug {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
 }
 
@@ -1210,7 +1869,7 @@ impl<T, const CAP: usize> Default for ArrayVec<T, CAP> {
     }
 }
 
-impl<T, const CAP: usize> PartialOrd for ArrayVec<T, CAP> where T: PartialOrd {
+impl<T, const CAP: usize, L: LenUint> PartialOrd for ArrayVec<T, CAP, L> where T: PartialOrd {
     fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
         (**self).partial_cmp(other)
     }
@@ -1232,7 +1891,7 @@ impl<T, const CAP: usize> PartialOrd for ArrayVec<T, CAP> where T: PartialOrd {
     }
 }
 
-impl<T, const CAP: usize> Ord for ArrayVec<T, CAP> where T: Ord {
+impl<T, const CAP: usize, L: LenUint> Ord for ArrayVec<T, CAP, L> where T: Ord {
     fn cmp(&self, other: &Self) -> cmp::Ordering {
         (**self).cmp(other)
     }
@@ -1242,7 +1901,7 @@ impl<T, const CAP: usize> Ord for ArrayVec<T, CAP> where T: Ord {
 /// `Write` appends written data to the end of the vector.
 ///
 /// Requires `features="std"`.
-impl<const CAP: usize> io::Write for ArrayVec<u8, CAP> {
+impl<const CAP: usize, L: LenUint> io::Write for ArrayVec<u8, CAP, L> {
     fn write(&mut self, data: &[u8]) -> io::Result<usize> {
         let len = cmp::min(self.remaining_capacity(), data.len());
         let _result = self.try_extend_from_slice(&data[..len]);
@@ -1252,9 +1911,111 @@ impl<const CAP: usize> io::Write for ArrayVec<u8, CAP> {
     fn flush(&mut self) -> io::Result<()> { Ok(()) }
 }
 
+#[cfg(feature="alloc")]
+/// Try to create an `ArrayVec` from a `Vec`, returning the `Vec` as the error
+/// if its elements do not fit.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use std::convert::TryFrom;
+///
+/// let array = ArrayVec::<_, 4>::try_from(vec![1, 2, 3]).unwrap();
+/// assert_eq!(&array[..], &[1, 2, 3]);
+/// let err = ArrayVec::<_, 2>::try_from(vec![1, 2, 3]).unwrap_err();
+/// assert_eq!(err.element(), vec![1, 2, 3]);
+/// ```
+impl<T, const CAP: usize, L: LenUint> std::convert::TryFrom<Vec<T>> for ArrayVec<T, CAP, L> {
+    type Error = CapacityError<Vec<T>>;
+
+    fn try_from(mut vec: Vec<T>) -> Result<Self, Self::Error> {
+        if vec.len() > CAP {
+            return Err(CapacityError::new(vec));
+        }
+        let mut array = Self::new_with_len_type();
+        unsafe {
+            // Move the elements; the vector no longer owns them after `set_len(0)`
+            ptr::copy_nonoverlapping(vec.as_ptr(), array.as_mut_ptr(), vec.len());
+            array.set_len(vec.len());
+            vec.set_len(0);
+        }
+        Ok(array)
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Move the elements into a `Vec`.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+///
+/// let array = ArrayVec::from([1, 2, 3]);
+/// let vec: Vec<_> = array.into();
+/// assert_eq!(vec, [1, 2, 3]);
+/// ```
+impl<T, const CAP: usize, L: LenUint> From<ArrayVec<T, CAP, L>> for Vec<T> {
+    fn from(array: ArrayVec<T, CAP, L>) -> Self {
+        let mut vec = Vec::with_capacity(array.len());
+        vec.extend(array);
+        vec
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Requires `features="alloc"`.
+impl<T, const CAP: usize, L: LenUint> From<ArrayVec<T, CAP, L>> for Box<[T]> {
+    fn from(array: ArrayVec<T, CAP, L>) -> Self {
+        Vec::from(array).into_boxed_slice()
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Move the elements into an owned `Cow`.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use std::borrow::Cow;
+///
+/// let cow = Cow::from(ArrayVec::from([1, 2, 3]));
+/// assert!(matches!(cow, Cow::Owned(_)));
+/// assert_eq!(cow.into_owned(), [1, 2, 3]);
+/// ```
+impl<'a, T: Clone, const CAP: usize, L: LenUint> From<ArrayVec<T, CAP, L>> for Cow<'a, [T]> {
+    fn from(array: ArrayVec<T, CAP, L>) -> Self {
+        Cow::Owned(array.into())
+    }
+}
+
+#[cfg(feature="alloc")]
+/// Borrow the elements as a `Cow`, which is turned into a `Vec` by
+/// `.into_owned()` through `ToOwned`.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::ArrayVec;
+/// use std::borrow::Cow;
+///
+/// let array = ArrayVec::from([1, 2, 3]);
+/// let cow = Cow::from(&array);
+/// assert!(matches!(cow, Cow::Borrowed(&[1, 2, 3])));
+/// let owned: Vec<_> = cow.into_owned();
+/// assert_eq!(owned, [1, 2, 3]);
+/// ```
+impl<'a, T: Clone, const CAP: usize, L: LenUint> From<&'a ArrayVec<T, CAP, L>> for Cow<'a, [T]> {
+    fn from(array: &'a ArrayVec<T, CAP, L>) -> Self {
+        Cow::Borrowed(array)
+    }
+}
+
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<T: Serialize, const CAP: usize> Serialize for ArrayVec<T, CAP> {
+impl<T: Serialize, const CAP: usize, L: LenUint> Serialize for ArrayVec<T, CAP, L> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: Serializer
     {
@@ -1264,17 +2025,17 @@ impl<T: Serialize, const CAP: usize> Serialize for ArrayVec<T, CAP> {
 
 #[cfg(feature="serde")]
 /// Requires crate feature `"serde"`
-impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T, CAP> {
+impl<'de, T: Deserialize<'de>, const CAP: usize, L: LenUint> Deserialize<'de> for ArrayVec<T, CAP, L> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where D: Deserializer<'de>
     {
         use serde::de::{Visitor, SeqAccess, Error};
         use std::marker::PhantomData;
 
-        struct ArrayVecVisitor<'de, T: Deserialize<'de>, const CAP: usize>(PhantomData<(&'de (), [T; CAP])>);
+        struct ArrayVecVisitor<'de, T: Deserialize<'de>, const CAP: usize, L>(PhantomData<(&'de (), [T; CAP], L)>);
 
-        impl<'de, T: Deserialize<'de>, const CAP: usize> Visitor<'de> for ArrayVecVisitor<'de, T, CAP> {
-            type Value = ArrayVec<T, CAP>;
+        impl<'de, T: Deserialize<'de>, const CAP: usize, L: LenUint> Visitor<'de> for ArrayVecVisitor<'de, T, CAP, L> {
+            type Value = ArrayVec<T, CAP, L>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 write!(formatter, "an array with no more than {} items", CAP)
@@ -1283,7 +2044,7 @@ impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T
             fn visit_seq<SA>(self, mut seq: SA) -> Result<Self::Value, SA::Error>
                 where SA: SeqAccess<'de>,
             {
-                let mut values = ArrayVec::<T, CAP>::new();
+                let mut values = ArrayVec::<T, CAP, L>::new_with_len_type();
 
                 while let Some(value) = seq.next_element()? {
                     if let Err(_) = values.try_push(value) {
@@ -1295,6 +2056,6 @@ impl<'de, T: Deserialize<'de>, const CAP: usize> Deserialize<'de> for ArrayVec<T
             }
         }
 
-        deserializer.deserialize_seq(ArrayVecVisitor::<T, CAP>(PhantomData))
+        deserializer.deserialize_seq(ArrayVecVisitor::<T, CAP, L>(PhantomData))
     }
 }
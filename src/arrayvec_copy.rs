@@ -0,0 +1,790 @@
+
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+#[cfg(feature="std")]
+use std::io;
+use std::iter;
+use std::mem::{self, ManuallyDrop, MaybeUninit};
+use std::ops::{Deref, DerefMut, RangeBounds};
+use std::ptr;
+use std::slice;
+
+#[cfg(feature="serde")]
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::arrayvec_impl::ArrayVecImpl;
+use crate::len_uint::len_to_usize;
+use crate::utils::MakeMaybeUninit;
+use crate::{ArrayVec, ArrayVecView, CapacityError, Drain, ExtractIf, IntoIter, LenUint, Splice};
+
+/// A vector with a fixed capacity, that is `Copy`.
+///
+/// The `ArrayVecCopy` is an [`ArrayVec`] for elements that are `Copy`. It does
+/// not need to drop its elements, so it is `Copy` itself and can be stored in
+/// `Copy` types.
+///
+/// It has the `ArrayVec` API, and its methods that return a vector by value
+/// return an `ArrayVecCopy`. It dereferences to a slice, and converts to and
+/// from an `ArrayVec` of the same capacity and length type.
+///
+/// ```
+/// use arrayvec::ArrayVecCopy;
+///
+/// #[derive(Clone, Copy)]
+/// struct Message {
+///     id: u32,
+///     values: ArrayVecCopy<u16, 8>,
+/// }
+///
+/// let mut message = Message { id: 1, values: ArrayVecCopy::new() };
+/// message.values.push(7);
+/// let copy = message;
+/// message.values.push(8);
+/// assert_eq!(&copy.values[..], &[7]);
+/// assert_eq!(&message.values[..], &[7, 8]);
+/// ```
+// `repr(C)` so that the layout matches `ArrayVec<T, CAP, L>`
+#[repr(C)]
+pub struct ArrayVecCopy<T: Copy, const CAP: usize, L: LenUint = u32> {
+    len: L,
+    // the `len` first elements of the array are initialized
+    xs: [MaybeUninit<T>; CAP],
+}
+
+// The length type does not take part in inference, so `new` is only for the
+// default one, like `Vec::new` is only for the global allocator.
+impl<T: Copy, const CAP: usize> ArrayVecCopy<T, CAP> {
+    /// Create a new empty `ArrayVecCopy`.
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// let mut array = ArrayVecCopy::<_, 16>::new();
+    /// array.push(1);
+    /// array.push(2);
+    /// assert_eq!(&array[..], &[1, 2]);
+    /// assert_eq!(array.capacity(), 16);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn new() -> ArrayVecCopy<T, CAP> {
+        ArrayVecCopy::new_with_len_type()
+    }
+
+    /// Create a new empty `ArrayVecCopy` (const fn).
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// static ARRAY: ArrayVecCopy<u8, 1024> = ArrayVecCopy::new_const();
+    /// ```
+    pub const fn new_const() -> ArrayVecCopy<T, CAP> {
+        ArrayVecCopy::new_with_len_type_const()
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> ArrayVecCopy<T, CAP, L> {
+    /// Create a new empty `ArrayVecCopy` with the length type `L`.
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// let mut array = ArrayVecCopy::<_, 16, u8>::new_with_len_type();
+    /// array.push(1);
+    /// assert_eq!(&array[..], &[1]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn new_with_len_type() -> ArrayVecCopy<T, CAP, L> {
+        assert_capacity_limit!(CAP, L);
+        ArrayVecCopy { xs: MakeMaybeUninit::ARRAY, len: L::ZERO }
+    }
+
+    /// Create a new empty `ArrayVecCopy` with the length type `L` (const fn).
+    ///
+    /// The maximum capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// static ARRAY: ArrayVecCopy<u8, 255, u8> = ArrayVecCopy::new_with_len_type_const();
+    /// ```
+    pub const fn new_with_len_type_const() -> ArrayVecCopy<T, CAP, L> {
+        assert_capacity_limit_const!(CAP, L);
+        ArrayVecCopy { xs: MakeMaybeUninit::ARRAY, len: L::ZERO }
+    }
+
+    /// Return the number of elements in the `ArrayVecCopy`.
+    #[inline(always)]
+    pub const fn len(&self) -> usize { len_to_usize(&self.len) }
+
+    /// Returns whether the `ArrayVecCopy` is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `ArrayVecCopy`.
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize { CAP }
+
+    /// Return true if the `ArrayVecCopy` is completely filled to its capacity,
+    /// false otherwise.
+    pub const fn is_full(&self) -> bool { self.len() == self.capacity() }
+
+    /// Returns the capacity left in the `ArrayVecCopy`.
+    pub const fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// ***Panics*** if the vector is already full.
+    #[track_caller]
+    pub fn push(&mut self, element: T) {
+        ArrayVecImpl::push(self, element)
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// **Errors** if the vector is already full, returning the element.
+    pub fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        ArrayVecImpl::try_push(self, element)
+    }
+
+    /// Push `element` to the end of the vector without checking the capacity.
+    ///
+    /// # Safety
+    ///
+    /// The vector must not be full.
+    pub unsafe fn push_unchecked(&mut self, element: T) {
+        ArrayVecImpl::push_unchecked(self, element)
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping
+    /// the rest.
+    pub fn truncate(&mut self, new_len: usize) {
+        ArrayVecImpl::truncate(self, new_len)
+    }
+
+    /// Remove all elements in the vector.
+    pub fn clear(&mut self) {
+        ArrayVecImpl::clear(self)
+    }
+
+    /// Resize the vector to `new_len`, filling new space with clones of
+    /// `value`. See [`ArrayVec::resize`].
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity.
+    #[track_caller]
+    pub fn resize(&mut self, new_len: usize, value: T) {
+        self.as_mut_arrayvec().resize(new_len, value)
+    }
+
+    /// Resize the vector to `new_len`, filling new space with clones of
+    /// `value`.
+    ///
+    /// **Errors** if `new_len` is greater than the capacity, returning the
+    /// value and leaving the vector unchanged.
+    pub fn try_resize(&mut self, new_len: usize, value: T) -> Result<(), CapacityError<T>> {
+        self.as_mut_arrayvec().try_resize(new_len, value)
+    }
+
+    /// Resize the vector to `new_len`, filling new space with the results of
+    /// calling `f`. See [`ArrayVec::resize_with`].
+    ///
+    /// ***Panics*** if `new_len` is greater than the capacity.
+    #[track_caller]
+    pub fn resize_with<F>(&mut self, new_len: usize, f: F)
+        where F: FnMut() -> T
+    {
+        self.as_mut_arrayvec().resize_with(new_len, f)
+    }
+
+    /// Resize the vector to `new_len`, filling new space with the results of
+    /// calling `f`.
+    ///
+    /// **Errors** if `new_len` is greater than the capacity, leaving the
+    /// vector unchanged.
+    pub fn try_resize_with<F>(&mut self, new_len: usize, f: F) -> Result<(), CapacityError>
+        where F: FnMut() -> T
+    {
+        self.as_mut_arrayvec().try_resize_with(new_len, f)
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// ***Panics*** if the vector is already full or if `index` is out of
+    /// bounds.
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.try_insert(index, element).unwrap()
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// **Errors** if the vector is already full, returning the element.
+    ///
+    /// ***Panics*** if `index` is out of bounds.
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), CapacityError<T>> {
+        if index > self.len() {
+            panic_oob!("ArrayVecCopy", "try_insert", index, self.len())
+        }
+        ArrayVecImpl::try_insert(self, index, element)
+    }
+
+    /// Remove the last element in the vector and return it.
+    ///
+    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    pub fn pop(&mut self) -> Option<T> {
+        ArrayVecImpl::pop(self)
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.swap_pop(index)
+            .unwrap_or_else(|| {
+                panic_oob!("ArrayVecCopy", "swap_remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    pub fn swap_pop(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::swap_pop(self, index)
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.pop_at(index)
+            .unwrap_or_else(|| {
+                panic_oob!("ArrayVecCopy", "remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    pub fn pop_at(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::pop_at(self, index)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, f: F)
+        where F: FnMut(&mut T) -> bool
+    {
+        ArrayVecImpl::retain(self, f)
+    }
+
+    /// Removes consecutive repeated elements. See [`ArrayVec::dedup`].
+    pub fn dedup(&mut self)
+        where T: PartialEq
+    {
+        self.as_mut_arrayvec().dedup()
+    }
+
+    /// Removes all but the first of consecutive elements that resolve to the
+    /// same key. See [`ArrayVec::dedup_by_key`].
+    pub fn dedup_by_key<F, K>(&mut self, key: F)
+        where F: FnMut(&mut T) -> K,
+              K: PartialEq,
+    {
+        self.as_mut_arrayvec().dedup_by_key(key)
+    }
+
+    /// Removes all but the first of consecutive elements that satisfy the
+    /// given equality relation. See [`ArrayVec::dedup_by`].
+    pub fn dedup_by<F>(&mut self, same_bucket: F)
+        where F: FnMut(&mut T, &mut T) -> bool
+    {
+        self.as_mut_arrayvec().dedup_by(same_bucket)
+    }
+
+    /// Create an iterator which removes and yields the elements in `range`
+    /// that match the `filter`. See [`ArrayVec::extract_if`].
+    pub fn extract_if<F, R>(&mut self, range: R, filter: F) -> ExtractIf<'_, T, F, CAP, L>
+        where F: FnMut(&mut T) -> bool,
+              R: RangeBounds<usize>,
+    {
+        self.as_mut_arrayvec().extract_if(range, filter)
+    }
+
+    /// Set the vector’s length without dropping or moving out elements
+    ///
+    /// # Safety
+    ///
+    /// The first `length` elements must be initialized, and `length` must not
+    /// be greater than the capacity.
+    pub unsafe fn set_len(&mut self, length: usize) {
+        ArrayVecImpl::set_len(self, length)
+    }
+
+    /// Copy all elements from the slice and append to the vector.
+    ///
+    /// **Errors** if the capacity left is smaller than the length of the
+    /// slice.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError> {
+        ArrayVecImpl::try_extend_from_slice(self, other)
+    }
+
+    /// Extend the vector with the elements of `iter` until it is full.
+    /// See [`ArrayVec::try_extend`].
+    ///
+    /// **Errors** if the iterator has more elements than fit, returning the
+    /// first element that did not fit and the rest of the iterator.
+    pub fn try_extend<I>(&mut self, iter: I) -> Result<(), (T, I::IntoIter)>
+        where I: IntoIterator<Item = T>
+    {
+        self.as_mut_arrayvec().try_extend(iter)
+    }
+
+    /// Create a vector from the elements of `iter`.
+    /// See [`ArrayVec::try_from_iter`].
+    ///
+    /// **Errors** if the iterator has more elements than fit, returning the
+    /// full vector, the first element that did not fit and the rest of the
+    /// iterator.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, (Self, T, I::IntoIter)>
+        where I: IntoIterator<Item = T>
+    {
+        ArrayVec::try_from_iter(iter)
+            .map(Self::from)
+            .map_err(|(array, element, rest)| (Self::from(array), element, rest))
+    }
+
+    /// Move all elements of `other` to the end of the vector, leaving `other`
+    /// empty.
+    ///
+    /// ***Panics*** if the elements of `other` do not fit.
+    #[track_caller]
+    pub fn append<const OTHER_CAP: usize, L2: LenUint>(&mut self, other: &mut ArrayVecCopy<T, OTHER_CAP, L2>) {
+        self.as_mut_arrayvec().append(other.as_mut_arrayvec())
+    }
+
+    /// Move all elements of `other` to the end of the vector, leaving `other`
+    /// empty.
+    ///
+    /// **Errors** if the elements of `other` do not fit, leaving both vectors
+    /// unchanged.
+    pub fn try_append<const OTHER_CAP: usize, L2: LenUint>(&mut self, other: &mut ArrayVecCopy<T, OTHER_CAP, L2>)
+        -> Result<(), CapacityError>
+    {
+        self.as_mut_arrayvec().try_append(other.as_mut_arrayvec())
+    }
+
+    /// Split the vector at `at`, returning the elements from `at` onwards in
+    /// a new vector with capacity `CAP2`.
+    ///
+    /// ***Panics*** if `at` is out of bounds or if the elements do not fit in
+    /// `CAP2`.
+    #[track_caller]
+    pub fn split_off<const CAP2: usize>(&mut self, at: usize) -> ArrayVecCopy<T, CAP2, L> {
+        self.as_mut_arrayvec().split_off(at).into()
+    }
+
+    /// Split the vector at `at`, returning the elements from `at` onwards in
+    /// a new vector with capacity `CAP2`.
+    ///
+    /// **Errors** if the elements do not fit in `CAP2`, leaving the vector
+    /// unchanged.
+    ///
+    /// ***Panics*** if `at` is out of bounds.
+    pub fn try_split_off<const CAP2: usize>(&mut self, at: usize)
+        -> Result<ArrayVecCopy<T, CAP2, L>, CapacityError>
+    {
+        self.as_mut_arrayvec().try_split_off(at).map(ArrayVecCopy::from)
+    }
+
+    /// Create a draining iterator that removes the specified range in the vector
+    /// and yields the removed items from start to end.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T, CAP, L>
+        where R: RangeBounds<usize>
+    {
+        self.as_mut_arrayvec().drain(range)
+    }
+
+    /// Create a splicing iterator that replaces the specified range in the
+    /// vector with `replace_with`. See [`ArrayVec::splice`].
+    ///
+    /// ***Panics*** if the range is out of bounds, or when the iterator is
+    /// dropped if the replacement does not fit.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, CAP, L>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+    {
+        self.as_mut_arrayvec().splice(range, replace_with)
+    }
+
+    /// Create a splicing iterator that replaces the specified range in the
+    /// vector with `replace_with`. See [`ArrayVec::try_splice`].
+    ///
+    /// **Errors** if the replacement does not fit, returning the replacement
+    /// and leaving the vector unchanged.
+    pub fn try_splice<R, I>(&mut self, range: R, replace_with: I)
+        -> Result<Splice<'_, I::IntoIter, CAP, L>, CapacityError<I::IntoIter>>
+        where R: RangeBounds<usize>,
+              I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        self.as_mut_arrayvec().try_splice(range, replace_with)
+    }
+
+    /// Return the inner fixed size array, if it is full to its capacity.
+    ///
+    /// **Errors** if the vector is not full, returning the vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// let array = ArrayVecCopy::from([1, 2]);
+    /// assert_eq!(array.into_inner(), Ok([1, 2]));
+    /// ```
+    pub fn into_inner(self) -> Result<[T; CAP], Self> {
+        self.into_arrayvec().into_inner().map_err(Self::from)
+    }
+
+    /// Return the inner fixed size array.
+    ///
+    /// # Safety
+    ///
+    /// The vector must be full to its capacity.
+    pub unsafe fn into_inner_unchecked(self) -> [T; CAP] {
+        self.into_arrayvec().into_inner_unchecked()
+    }
+
+    /// Convert into a vector with capacity `CAP2`.
+    ///
+    /// The new capacity must be at least as large as the current capacity;
+    /// this is checked at compile time.
+    pub fn into_capacity<const CAP2: usize>(self) -> ArrayVecCopy<T, CAP2, L> {
+        self.into_arrayvec().into_capacity().into()
+    }
+
+    /// Convert into a vector with capacity `CAP2`.
+    ///
+    /// **Errors** if the elements do not fit in `CAP2`, returning the vector.
+    pub fn try_into_capacity<const CAP2: usize>(self) -> Result<ArrayVecCopy<T, CAP2, L>, Self> {
+        self.into_arrayvec().try_into_capacity()
+            .map(ArrayVecCopy::from)
+            .map_err(Self::from)
+    }
+
+    /// Returns the vector, replacing the original with a new empty vector.
+    ///
+    /// ```
+    /// use arrayvec::ArrayVecCopy;
+    ///
+    /// let mut v = ArrayVecCopy::from([0, 1, 2, 3]);
+    /// assert_eq!([0, 1, 2, 3], v.take().into_inner().unwrap());
+    /// assert!(v.is_empty());
+    /// ```
+    pub fn take(&mut self) -> Self {
+        mem::replace(self, Self::new_with_len_type())
+    }
+
+    /// Return a slice containing all elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        ArrayVecImpl::as_slice(self)
+    }
+
+    /// Return a mutable slice containing all elements of the vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        ArrayVecImpl::as_mut_slice(self)
+    }
+
+    /// Return a raw pointer to the vector's buffer.
+    pub fn as_ptr(&self) -> *const T {
+        ArrayVecImpl::as_ptr(self)
+    }
+
+    /// Return a raw mutable pointer to the vector's buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        ArrayVecImpl::as_mut_ptr(self)
+    }
+
+    /// Return a capacity-erased view of the vector.
+    pub fn as_view(&self) -> &ArrayVecView<T, L> {
+        // SAFETY: `ArrayVecView<T, L>` has the layout of `ArrayVecCopy<T, CAP, L>`
+        unsafe { ArrayVecView::from_raw(self as *const Self as *const u8, CAP) }
+    }
+
+    /// Return a mutable capacity-erased view of the vector.
+    pub fn as_mut_view(&mut self) -> &mut ArrayVecView<T, L> {
+        // SAFETY: `ArrayVecView<T, L>` has the layout of `ArrayVecCopy<T, CAP, L>`
+        unsafe { ArrayVecView::from_raw_mut(self as *mut Self as *mut u8, CAP) }
+    }
+
+    /// Convert into an `ArrayVec`.
+    ///
+    /// ```
+    /// use arrayvec::{ArrayVec, ArrayVecCopy};
+    ///
+    /// let array = ArrayVecCopy::from([1, 2, 3]);
+    /// let array: ArrayVec<_, 3> = array.into_arrayvec();
+    /// assert_eq!(array.into_inner(), Ok([1, 2, 3]));
+    /// ```
+    pub fn into_arrayvec(self) -> ArrayVec<T, CAP, L> {
+        // SAFETY: the types have the same layout
+        unsafe { ptr::read(&self as *const Self as *const ArrayVec<T, CAP, L>) }
+    }
+
+    /// Borrow the vector as an `ArrayVec`, for the methods that are not
+    /// shared through `ArrayVecImpl`.
+    fn as_mut_arrayvec(&mut self) -> &mut ArrayVec<T, CAP, L> {
+        // SAFETY: the types have the same layout, and since `T: Copy`, the
+        // elements an `ArrayVec` removes through the reference do not need to
+        // be dropped.
+        unsafe { &mut *(self as *mut Self as *mut ArrayVec<T, CAP, L>) }
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> ArrayVecImpl for ArrayVecCopy<T, CAP, L> {
+    type Item = T;
+
+    fn capacity(&self) -> usize { CAP }
+
+    fn len(&self) -> usize { self.len() }
+
+    unsafe fn set_len(&mut self, length: usize) {
+        debug_assert!(length <= CAP);
+        self.len = L::from_usize(length);
+    }
+
+    fn as_ptr(&self) -> *const Self::Item {
+        self.xs.as_ptr() as _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut Self::Item {
+        self.xs.as_mut_ptr() as _
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> Clone for ArrayVecCopy<T, CAP, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> Copy for ArrayVecCopy<T, CAP, L> { }
+
+impl<T: Copy, const CAP: usize, L: LenUint> Deref for ArrayVecCopy<T, CAP, L> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> DerefMut for ArrayVecCopy<T, CAP, L> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Create an `ArrayVecCopy` from an array.
+///
+/// ```
+/// use arrayvec::ArrayVecCopy;
+///
+/// let array = ArrayVecCopy::from([1, 2, 3]);
+/// assert_eq!(array.len(), 3);
+/// assert_eq!(array.capacity(), 3);
+/// ```
+impl<T: Copy, const CAP: usize> From<[T; CAP]> for ArrayVecCopy<T, CAP> {
+    #[track_caller]
+    fn from(array: [T; CAP]) -> Self {
+        ArrayVec::from(array).into()
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> From<ArrayVec<T, CAP, L>> for ArrayVecCopy<T, CAP, L> {
+    fn from(array: ArrayVec<T, CAP, L>) -> Self {
+        let array = ManuallyDrop::new(array);
+        // SAFETY: the types have the same layout
+        unsafe { ptr::read(&*array as *const ArrayVec<T, CAP, L> as *const Self) }
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> From<ArrayVecCopy<T, CAP, L>> for ArrayVec<T, CAP, L> {
+    fn from(array: ArrayVecCopy<T, CAP, L>) -> Self {
+        array.into_arrayvec()
+    }
+}
+
+/// Try to create an `ArrayVecCopy` from a slice. This will return an error if
+/// the slice was too big to fit.
+impl<T: Copy, const CAP: usize, L: LenUint> TryFrom<&[T]> for ArrayVecCopy<T, CAP, L> {
+    type Error = CapacityError;
+
+    fn try_from(slice: &[T]) -> Result<Self, Self::Error> {
+        ArrayVec::try_from(slice).map(Self::from)
+    }
+}
+
+impl<'a, T: Copy + 'a, const CAP: usize, L: LenUint> IntoIterator for &'a ArrayVecCopy<T, CAP, L> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, T: Copy + 'a, const CAP: usize, L: LenUint> IntoIterator for &'a mut ArrayVecCopy<T, CAP, L> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// Iterate the `ArrayVecCopy` with each element by value.
+impl<T: Copy, const CAP: usize, L: LenUint> IntoIterator for ArrayVecCopy<T, CAP, L> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP, L>;
+    fn into_iter(self) -> IntoIter<T, CAP, L> {
+        self.into_arrayvec().into_iter()
+    }
+}
+
+/// Extend the `ArrayVecCopy` with an iterator.
+///
+/// ***Panics*** if extending the vector exceeds its capacity.
+impl<T: Copy, const CAP: usize, L: LenUint> Extend<T> for ArrayVecCopy<T, CAP, L> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        self.as_mut_arrayvec().extend(iter)
+    }
+}
+
+/// Create an `ArrayVecCopy` from an iterator.
+///
+/// ***Panics*** if the number of elements in the iterator exceeds the
+/// arrayvec's capacity.
+impl<T: Copy, const CAP: usize, L: LenUint> iter::FromIterator<T> for ArrayVecCopy<T, CAP, L> {
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        iter.into_iter().collect::<ArrayVec<T, CAP, L>>().into()
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> Hash for ArrayVecCopy<T, CAP, L>
+    where T: Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> PartialEq for ArrayVecCopy<T, CAP, L>
+    where T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> PartialEq<[T]> for ArrayVecCopy<T, CAP, L>
+    where T: PartialEq
+{
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> Eq for ArrayVecCopy<T, CAP, L> where T: Eq { }
+
+impl<T: Copy, const CAP: usize, L: LenUint> Borrow<[T]> for ArrayVecCopy<T, CAP, L> {
+    fn borrow(&self) -> &[T] { self }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> BorrowMut<[T]> for ArrayVecCopy<T, CAP, L> {
+    fn borrow_mut(&mut self) -> &mut [T] { self }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> AsRef<[T]> for ArrayVecCopy<T, CAP, L> {
+    fn as_ref(&self) -> &[T] { self }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> AsMut<[T]> for ArrayVecCopy<T, CAP, L> {
+    fn as_mut(&mut self) -> &mut [T] { self }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> fmt::Debug for ArrayVecCopy<T, CAP, L> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<T: Copy, const CAP: usize> Default for ArrayVecCopy<T, CAP> {
+    /// Return an empty array
+    fn default() -> ArrayVecCopy<T, CAP> {
+        ArrayVecCopy::new()
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> PartialOrd for ArrayVecCopy<T, CAP, L> where T: PartialOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: Copy, const CAP: usize, L: LenUint> Ord for ArrayVecCopy<T, CAP, L> where T: Ord {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+#[cfg(feature="std")]
+/// `Write` appends written data to the end of the vector.
+///
+/// Requires `features="std"`.
+impl<const CAP: usize, L: LenUint> io::Write for ArrayVecCopy<u8, CAP, L> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let len = cmp::min(self.remaining_capacity(), data.len());
+        let _result = self.try_extend_from_slice(&data[..len]);
+        debug_assert!(_result.is_ok());
+        Ok(len)
+    }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+#[cfg(feature = "zeroize")]
+/// "Best efforts" zeroing of the `ArrayVecCopy`'s buffer when the `zeroize` feature is enabled.
+///
+/// The length is set to 0, and the buffer is zeroized.
+/// Cannot ensure that previous copies of the `ArrayVecCopy` did not leave values on the stack.
+impl<Z: zeroize::Zeroize + Copy, const CAP: usize, L: LenUint> zeroize::Zeroize for ArrayVecCopy<Z, CAP, L> {
+    fn zeroize(&mut self) {
+        self.as_mut_arrayvec().zeroize()
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<T: Copy + Serialize, const CAP: usize, L: LenUint> Serialize for ArrayVecCopy<T, CAP, L> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self)
+    }
+}
+
+#[cfg(feature="serde")]
+/// Requires crate feature `"serde"`
+impl<'de, T: Copy + Deserialize<'de>, const CAP: usize, L: LenUint> Deserialize<'de> for ArrayVecCopy<T, CAP, L> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        ArrayVec::deserialize(deserializer).map(Self::from)
+    }
+}
@@ -7,7 +7,8 @@ use crate::CapacityError;
 /// for length and element access.
 pub(crate) trait ArrayVecImpl {
     type Item;
-    const CAPACITY: usize;
+
+    fn capacity(&self) -> usize;
 
     fn len(&self) -> usize;
 
@@ -41,7 +42,7 @@ pub(crate) trait ArrayVecImpl {
     }
 
     fn try_push(&mut self, element: Self::Item) -> Result<(), CapacityError<Self::Item>> {
-        if self.len() < Self::CAPACITY {
+        if self.len() < self.capacity() {
             unsafe {
                 self.push_unchecked(element);
             }
@@ -53,7 +54,7 @@ pub(crate) trait ArrayVecImpl {
 
     unsafe fn push_unchecked(&mut self, element: Self::Item) {
         let len = self.len();
-        debug_assert!(len < Self::CAPACITY);
+        debug_assert!(len < self.capacity());
         ptr::write(self.as_mut_ptr().add(len), element);
         self.set_len(len + 1);
     }
@@ -69,6 +70,172 @@ pub(crate) trait ArrayVecImpl {
         }
     }
 
+    /// Insert `element` at `index`, which must be at most the length.
+    fn try_insert(&mut self, index: usize, element: Self::Item) -> Result<(), CapacityError<Self::Item>> {
+        let len = self.len();
+        debug_assert!(index <= len);
+        if len == self.capacity() {
+            return Err(CapacityError::new(element));
+        }
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            // Shift everything over to make space, then overwrite the first
+            // copy of the `index`th element.
+            ptr::copy(p, p.add(1), len - index);
+            ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+        Ok(())
+    }
+
+    fn pop_at(&mut self, index: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            let element = ptr::read(p);
+            ptr::copy(p.add(1), p, len - index - 1);
+            self.set_len(len - 1);
+            Some(element)
+        }
+    }
+
+    fn swap_pop(&mut self, index: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+        self.as_mut_slice().swap(index, len - 1);
+        self.pop()
+    }
+
+    fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&mut Self::Item) -> bool
+    {
+        // Check the implementation of
+        // https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain
+        // for safety arguments (especially regarding panics in f and when
+        // dropping elements). Implementation closely mirrored here.
+
+        let original_len = self.len();
+        unsafe { self.set_len(0) };
+
+        struct BackshiftOnDrop<'a, V: ArrayVecImpl + ?Sized> {
+            v: &'a mut V,
+            processed_len: usize,
+            deleted_cnt: usize,
+            original_len: usize,
+        }
+
+        impl<V: ArrayVecImpl + ?Sized> Drop for BackshiftOnDrop<'_, V> {
+            fn drop(&mut self) {
+                if self.deleted_cnt > 0 {
+                    unsafe {
+                        ptr::copy(
+                            self.v.as_ptr().add(self.processed_len),
+                            self.v.as_mut_ptr().add(self.processed_len - self.deleted_cnt),
+                            self.original_len - self.processed_len
+                        );
+                    }
+                }
+                unsafe {
+                    self.v.set_len(self.original_len - self.deleted_cnt);
+                }
+            }
+        }
+
+        let mut g = BackshiftOnDrop { v: self, processed_len: 0, deleted_cnt: 0, original_len };
+
+        #[inline(always)]
+        fn process_one<F, V, const DELETED: bool>(
+            f: &mut F,
+            g: &mut BackshiftOnDrop<'_, V>
+        ) -> bool
+            where V: ArrayVecImpl + ?Sized,
+                  F: FnMut(&mut V::Item) -> bool,
+        {
+            let cur = unsafe { g.v.as_mut_ptr().add(g.processed_len) };
+            if !f(unsafe { &mut *cur }) {
+                g.processed_len += 1;
+                g.deleted_cnt += 1;
+                unsafe { ptr::drop_in_place(cur) };
+                return false;
+            }
+            if DELETED {
+                unsafe {
+                    let hole_slot = cur.sub(g.deleted_cnt);
+                    ptr::copy_nonoverlapping(cur, hole_slot, 1);
+                }
+            }
+            g.processed_len += 1;
+            true
+        }
+
+        // Stage 1: Nothing was deleted.
+        while g.processed_len != original_len {
+            if !process_one::<F, Self, false>(&mut f, &mut g) {
+                break;
+            }
+        }
+
+        // Stage 2: Some elements were deleted.
+        while g.processed_len != original_len {
+            process_one::<F, Self, true>(&mut f, &mut g);
+        }
+
+        drop(g);
+    }
+
+    fn try_extend_from_slice(&mut self, other: &[Self::Item]) -> Result<(), CapacityError>
+        where Self::Item: Copy,
+    {
+        let len = self.len();
+        if self.capacity() - len < other.len() {
+            return Err(CapacityError::new(()));
+        }
+        unsafe {
+            let dst = self.as_mut_ptr().add(len);
+            ptr::copy_nonoverlapping(other.as_ptr(), dst, other.len());
+            self.set_len(len + other.len());
+        }
+        Ok(())
+    }
+
+    /// Create a draining iterator for the range `start..end`.
+    ///
+    /// ***Panics*** if the range is not within the vector.
+    fn drain_range(&mut self, start: usize, end: usize) -> DrainImpl<'_, Self> {
+        // Memory safety
+        //
+        // When the Drain is first created, it shortens the length of
+        // the source vector to make sure no uninitialized or moved-from elements
+        // are accessible at all if the Drain's destructor never gets to run.
+        //
+        // Drain will ptr::read out the values to remove.
+        // When finished, remaining tail of the vec is copied back to cover
+        // the hole, and the vector length is restored to the new length.
+        let len = self.len();
+
+        // bounds check happens here (before length is changed!)
+        let _ = &self.as_slice()[start..end];
+
+        // Derive all accesses from the one raw pointer, so that creating it does
+        // not invalidate the iterator's borrow of the range.
+        let vec: *mut Self = self;
+        unsafe {
+            (*vec).set_len(start);
+            let range_slice = slice::from_raw_parts((*vec).as_ptr().add(start), end - start);
+            DrainImpl {
+                tail_start: end,
+                tail_len: len - end,
+                iter: range_slice.iter(),
+                vec,
+            }
+        }
+    }
+
     fn clear(&mut self) {
         self.truncate(0)
     }
@@ -85,3 +252,94 @@ pub(crate) trait ArrayVecImpl {
     }
 }
 
+
+/// The draining iterator shared by the vectors, generic over their storage.
+///
+/// `ArrayVec`, `ArrayVecView` and `SliceVec` wrap it in their own `Drain`.
+pub(crate) struct DrainImpl<'a, V: ArrayVecImpl + ?Sized> {
+    /// Index of tail to preserve
+    pub(crate) tail_start: usize,
+    /// Length of tail
+    pub(crate) tail_len: usize,
+    /// Current remaining range to remove
+    iter: slice::Iter<'a, V::Item>,
+    vec: *mut V,
+}
+
+impl<'a, V: ArrayVecImpl + ?Sized> Iterator for DrainImpl<'a, V> {
+    type Item = V::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|elt|
+            unsafe {
+                ptr::read(elt as *const _)
+            }
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, V: ArrayVecImpl + ?Sized> DoubleEndedIterator for DrainImpl<'a, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|elt|
+            unsafe {
+                ptr::read(elt as *const _)
+            }
+        )
+    }
+}
+
+impl<'a, V: ArrayVecImpl + ?Sized> DrainImpl<'a, V> {
+    /// Fill the gap between the vector's length and the tail with elements
+    /// from `replace_with`. Return `true` if the gap was filled.
+    ///
+    /// Safety: the drained range must be exhausted.
+    pub(crate) unsafe fn fill<I>(&mut self, replace_with: &mut I) -> bool
+        where I: Iterator<Item = V::Item>
+    {
+        let vec = &mut *self.vec;
+        while vec.len() < self.tail_start {
+            match replace_with.next() {
+                Some(elt) => vec.push_unchecked(elt),
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Move the tail to start at `new_tail_start`.
+    ///
+    /// Safety: the drained range must be exhausted, and the new tail position
+    /// must be at least the vector's length and leave the tail within capacity.
+    pub(crate) unsafe fn move_tail(&mut self, new_tail_start: usize) {
+        let vec = &mut *self.vec;
+        debug_assert!(vec.len() <= new_tail_start && new_tail_start + self.tail_len <= vec.capacity());
+        let ptr = vec.as_mut_ptr();
+        ptr::copy(ptr.add(self.tail_start), ptr.add(new_tail_start), self.tail_len);
+        self.tail_start = new_tail_start;
+    }
+}
+
+impl<'a, V: ArrayVecImpl + ?Sized> Drop for DrainImpl<'a, V> {
+    fn drop(&mut self) {
+        // len is currently 0 so panicking while dropping will not cause a double drop.
+
+        // exhaust self first
+        self.by_ref().for_each(drop);
+
+        if self.tail_len > 0 {
+            unsafe {
+                let source_vec = &mut *self.vec;
+                // memmove back untouched tail, update to new length
+                let start = source_vec.len();
+                let tail = self.tail_start;
+                let ptr = source_vec.as_mut_ptr();
+                ptr::copy(ptr.add(tail), ptr.add(start), self.tail_len);
+                source_vec.set_len(start + self.tail_len);
+            }
+        }
+    }
+}
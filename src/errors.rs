@@ -1,4 +1,5 @@
 use std::fmt;
+use std::str::Utf8Error;
 #[cfg(feature="std")]
 use std::any::Any;
 #[cfg(feature="std")]
@@ -47,3 +48,96 @@ impl<T> fmt::Debug for CapacityError<T> {
     }
 }
 
+
+/// Error value returned by [`ArrayString::from_utf8`](crate::ArrayString::from_utf8)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FromUtf8Error {
+    /// The bytes are not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The bytes do not fit in the capacity of the string.
+    Capacity(CapacityError),
+}
+
+#[cfg(feature="std")]
+/// Requires `features="std"`.
+impl Error for FromUtf8Error {}
+
+impl fmt::Display for FromUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromUtf8Error::Utf8(e) => fmt::Display::fmt(e, f),
+            FromUtf8Error::Capacity(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+/// Error value returned by [`ArrayString::from_utf16`](crate::ArrayString::from_utf16)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FromUtf16Error {
+    /// The input contains an unpaired surrogate.
+    Utf16,
+    /// The decoded string does not fit in the capacity of the string.
+    Capacity(CapacityError),
+}
+
+#[cfg(feature="std")]
+/// Requires `features="std"`.
+impl Error for FromUtf16Error {}
+
+impl fmt::Display for FromUtf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromUtf16Error::Utf16 => write!(f, "invalid utf-16: lone surrogate found"),
+            FromUtf16Error::Capacity(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+/// Error value returned when creating an [`ArrayCString`](crate::ArrayCString)
+///
+/// Requires `features="std"`.
+#[cfg(feature="std")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CStringError {
+    /// The bytes contain a nul byte, at the given position in the string.
+    InteriorNul(usize),
+    /// The bytes and the nul terminator do not fit in the capacity of the string.
+    Capacity(CapacityError),
+}
+
+#[cfg(feature="std")]
+/// Requires `features="std"`.
+impl Error for CStringError {}
+
+#[cfg(feature="std")]
+impl fmt::Display for CStringError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CStringError::InteriorNul(pos) => write!(f, "nul byte found in provided data at position: {}", pos),
+            CStringError::Capacity(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+/// Error value returned when creating an [`ArrayAsciiString`](crate::ArrayAsciiString)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AsciiError {
+    /// The input contains a non-ASCII character, at the given byte position
+    /// in the string.
+    NonAscii(usize),
+    /// The input does not fit in the capacity of the string.
+    Capacity(CapacityError),
+}
+
+#[cfg(feature="std")]
+/// Requires `features="std"`.
+impl Error for AsciiError {}
+
+impl fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AsciiError::NonAscii(pos) => write!(f, "non-ASCII character found at position: {}", pos),
+            AsciiError::Capacity(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::mem;
+
+/// An unsigned integer type used to store the length of an
+/// [`ArrayVec`](crate::ArrayVec) or [`ArrayString`](crate::ArrayString).
+///
+/// It is implemented for `u8`, `u16`, `u32` and `usize`, and the capacity of
+/// the collection must not be greater than the largest value of the type.
+/// A smaller type makes the collection smaller, for example
+/// `ArrayString<15, u8>` is 16 bytes while `ArrayString<15>` is 20 bytes.
+///
+/// `new` and `Default` create collections with the default `u32` length type,
+/// and `new_with_len_type` with any length type.
+///
+/// This trait is sealed and can not be implemented outside of arrayvec.
+pub trait LenUint: private::Sealed + Copy + 'static {
+    /// The largest supported capacity when using this length type.
+    const MAX: usize;
+
+    #[doc(hidden)]
+    const ZERO: Self;
+
+    #[doc(hidden)]
+    fn from_usize(n: usize) -> Self;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_len_uint {
+    ($($t:ty),*) => {$(
+        impl private::Sealed for $t {}
+
+        impl LenUint for $t {
+            const MAX: usize = if mem::size_of::<$t>() < mem::size_of::<usize>() {
+                <$t>::MAX as usize
+            } else {
+                usize::MAX
+            };
+
+            const ZERO: Self = 0;
+
+            #[inline(always)]
+            fn from_usize(n: usize) -> Self {
+                debug_assert!(n <= <Self as LenUint>::MAX);
+                n as $t
+            }
+        }
+    )*}
+}
+
+impl_len_uint!(u8, u16, u32, usize);
+
+/// Convert a length to `usize`, in a const fn.
+///
+/// Trait methods can not be called in a const fn, so this reads the integer
+/// by its size instead.
+#[inline(always)]
+pub(crate) const fn len_to_usize<L: LenUint>(len: &L) -> usize {
+    let ptr = len as *const L;
+    // SAFETY: `LenUint` is sealed and only implemented for the unsigned
+    // integer types, which are told apart by their size.
+    unsafe {
+        match mem::size_of::<L>() {
+            1 => *(ptr as *const u8) as usize,
+            2 => *(ptr as *const u16) as usize,
+            4 => *(ptr as *const u32) as usize,
+            _ => *(ptr as *const usize),
+        }
+    }
+}
@@ -1,11 +1,27 @@
 //! **arrayvec** provides the types [`ArrayVec`] and [`ArrayString`]: 
 //! array-backed vector and string types, which store their contents inline.
+//! It also provides [`ArrayDeque`], an array-backed double-ended queue,
+//! [`ArrayAsciiString`], an array-backed string restricted to ASCII, and
+//! [`ArrayVecCopy`], an `ArrayVec` that is `Copy`. [`ArrayVecView`] and
+//! [`ArrayStringView`] are views of them with the capacity erased, and
+//! [`SliceVec`] is a vector in borrowed storage. [`ArrayVecChunksExt`]
+//! batches the elements of an iterator into `ArrayVec`s.
 //!
 //! The arrayvec package has the following cargo features:
 //!
 //! - `std`
 //!   - Optional, enabled by default
 //!   - Use libstd; disable to use `no_std` instead.
+//!   - Provides [`ArrayCString`]
+//!   - Implies `alloc`
+//!
+//! - `alloc`
+//!   - Optional, enabled by `std`
+//!   - Use liballoc, for targets with a heap but without libstd.
+//!   - Provides conversions between the array types and `Vec`, `Box`,
+//!     `String` and `Cow`
+//!   - Provides [`SpillVec`] and [`SpillString`], which move their contents to
+//!     the heap when they outgrow their array.
 //!
 //! - `serde`
 //!   - Optional
@@ -17,9 +33,9 @@
 //!
 //! ## Rust Version
 //!
-//! This version of arrayvec requires Rust 1.51 or later.
+//! This version of arrayvec requires Rust 1.61 or later.
 //!
-#![doc(html_root_url="https://docs.rs/arrayvec/0.7/")]
+#![doc(html_root_url="https://docs.rs/arrayvec/0.8/")]
 #![cfg_attr(not(feature="std"), no_std)]
 
 #[cfg(feature="serde")]
@@ -28,36 +44,69 @@ extern crate serde;
 #[cfg(not(feature="std"))]
 extern crate core as std;
 
-pub(crate) type LenUint = u32;
+#[cfg(feature="alloc")]
+extern crate alloc;
 
 macro_rules! assert_capacity_limit {
-    ($cap:expr) => {
-        if std::mem::size_of::<usize>() > std::mem::size_of::<LenUint>() {
-            if $cap > LenUint::MAX as usize {
-                panic!("ArrayVec: largest supported capacity is u32::MAX")
-            }
+    ($cap:expr, $len:ty) => {
+        if $cap > <$len as crate::LenUint>::MAX {
+            panic!("ArrayVec: largest supported capacity is {}::MAX", std::any::type_name::<$len>())
         }
     }
 }
 
 macro_rules! assert_capacity_limit_const {
-    ($cap:expr) => {
-        if std::mem::size_of::<usize>() > std::mem::size_of::<LenUint>() {
-            if $cap > LenUint::MAX as usize {
-                [/*ArrayVec: largest supported capacity is u32::MAX*/][$cap]
-            }
+    ($cap:expr, $len:ty) => {
+        if $cap > <$len as crate::LenUint>::MAX {
+            [/*ArrayVec: largest supported capacity is LenUint::MAX*/][$cap]
         }
     }
 }
 
+macro_rules! panic_oob {
+    ($type_name:expr, $method_name:expr, $index:expr, $len:expr) => {
+        panic!(concat!($type_name, "::", $method_name, ": index {} is out of bounds in vector of length {}"),
+               $index, $len)
+    }
+}
+
 mod arrayvec_impl;
 mod arrayvec;
-mod array_string;
+mod arrayvec_copy;
+mod array_ascii_string;
+pub mod array_deque;
+#[cfg(feature="std")]
+mod array_cstring;
+pub mod array_string;
+mod array_vec_chunks;
+pub mod array_view;
 mod char;
 mod errors;
+mod len_uint;
+mod macros;
+pub mod slice_vec;
+#[cfg(feature="alloc")]
+mod spill_string;
+#[cfg(feature="alloc")]
+pub mod spill_vec;
 mod utils;
 
-pub use crate::array_string::ArrayString;
-pub use crate::errors::CapacityError;
+pub use crate::array_ascii_string::ArrayAsciiString;
+pub use crate::array_deque::ArrayDeque;
+#[cfg(feature="std")]
+pub use crate::array_cstring::ArrayCString;
+pub use crate::array_string::{ArrayString, PushStr, TruncatingWriter};
+pub use crate::array_vec_chunks::{ArrayVecChunks, ArrayVecChunksExt};
+pub use crate::array_view::{ArrayVecView, ArrayStringView};
+pub use crate::len_uint::LenUint;
+pub use crate::errors::{AsciiError, CapacityError, FromUtf8Error, FromUtf16Error};
+#[cfg(feature="std")]
+pub use crate::errors::CStringError;
 
-pub use crate::arrayvec::{ArrayVec, IntoIter, Drain};
+pub use crate::arrayvec::{ArrayVec, IntoIter, Drain, ExtractIf, Splice};
+pub use crate::arrayvec_copy::ArrayVecCopy;
+pub use crate::slice_vec::SliceVec;
+#[cfg(feature="alloc")]
+pub use crate::spill_string::SpillString;
+#[cfg(feature="alloc")]
+pub use crate::spill_vec::SpillVec;
@@ -0,0 +1,132 @@
+/// Create an [`ArrayVec`](crate::ArrayVec) containing the arguments.
+///
+/// The capacity is inferred from the context, or given explicitly before `=>`.
+/// When the elements are listed, it is checked at compile time that they fit
+/// in the capacity.
+///
+/// - Create an empty `ArrayVec`:
+///
+/// ```
+/// use arrayvec::{arrayvec, ArrayVec};
+///
+/// let v: ArrayVec<i32, 4> = arrayvec![];
+/// assert!(v.is_empty());
+/// ```
+///
+/// - Create an `ArrayVec` containing a given list of elements:
+///
+/// ```
+/// use arrayvec::{arrayvec, ArrayVec};
+///
+/// let v: ArrayVec<_, 4> = arrayvec![1, 2, 3];
+/// assert_eq!(&v[..], &[1, 2, 3]);
+///
+/// let v = arrayvec![8 => "a", "b"];
+/// assert_eq!(&v[..], &["a", "b"]);
+/// assert_eq!(v.capacity(), 8);
+/// ```
+///
+/// - Create an `ArrayVec` from a given element and length, cloning the element.
+///   ***Panics*** if the length is greater than the capacity.
+///
+/// ```
+/// use arrayvec::{arrayvec, ArrayVec};
+///
+/// let v: ArrayVec<_, 4> = arrayvec![0; 3];
+/// assert_eq!(&v[..], &[0, 0, 0]);
+///
+/// let v = arrayvec![4 => String::new(); 2];
+/// assert_eq!(v.len(), 2);
+/// ```
+///
+/// Listing more elements than fit is a compile time error:
+///
+/// ```compile_fail
+/// use arrayvec::arrayvec;
+///
+/// let v = arrayvec![2 => 1, 2, 3];
+/// ```
+#[macro_export]
+macro_rules! arrayvec {
+    () => {
+        $crate::ArrayVec::new()
+    };
+    ($cap:expr =>) => {
+        $crate::ArrayVec::<_, { $cap }>::new()
+    };
+    ($cap:expr => $elem:expr; $n:expr) => {{
+        let mut v = $crate::ArrayVec::<_, { $cap }>::new();
+        v.resize($n, $elem);
+        v
+    }};
+    ($cap:expr => $($x:expr),+ $(,)?) => {
+        $crate::ArrayVec::from([$($x),+]).into_capacity::<{ $cap }>()
+    };
+    ($elem:expr; $n:expr) => {{
+        let mut v = $crate::ArrayVec::new();
+        v.resize($n, $elem);
+        v
+    }};
+    ($($x:expr),+ $(,)?) => {
+        $crate::ArrayVec::from([$($x),+]).into_capacity()
+    };
+}
+
+/// Create an [`ArrayString`](crate::ArrayString) with capacity `CAP` using
+/// interpolation of runtime expressions, like `format!`.
+///
+/// Returns `Result<ArrayString<CAP>, CapacityError>`, with an error if the
+/// formatted string does not fit in the capacity.
+///
+/// ```
+/// use arrayvec::array_format;
+///
+/// let s = array_format!(16, "{}-{:02}", "id", 7).unwrap();
+/// assert_eq!(&s[..], "id-07");
+///
+/// assert!(array_format!(4, "{}", 12345).is_err());
+/// ```
+#[macro_export]
+macro_rules! array_format {
+    ($cap:expr, $($arg:tt)*) => {
+        <$crate::ArrayString<{ $cap }> as ::core::convert::TryFrom<::core::fmt::Arguments<'_>>>
+            ::try_from(::core::format_args!($($arg)*))
+            .map_err($crate::CapacityError::simplify)
+    };
+}
+
+/// Create an [`ArrayString`](crate::ArrayString) containing the given string
+/// slice.
+///
+/// The capacity is inferred from the context, or given explicitly before `=>`.
+///
+/// ***Panics*** if the string does not fit in the capacity.
+///
+/// ```
+/// use arrayvec::{array_string, ArrayString};
+///
+/// let s: ArrayString<8> = array_string!();
+/// assert!(s.is_empty());
+///
+/// let s: ArrayString<8> = array_string!("hello");
+/// assert_eq!(&s[..], "hello");
+///
+/// let s = array_string!(16 => "hello");
+/// assert_eq!(&s[..], "hello");
+/// assert_eq!(s.capacity(), 16);
+/// ```
+#[macro_export]
+macro_rules! array_string {
+    () => {
+        $crate::ArrayString::new()
+    };
+    ($cap:expr =>) => {
+        $crate::ArrayString::<{ $cap }>::new()
+    };
+    ($cap:expr => $s:expr) => {
+        $crate::ArrayString::<{ $cap }>::from($s).unwrap()
+    };
+    ($s:expr) => {
+        $crate::ArrayString::from($s).unwrap()
+    };
+}
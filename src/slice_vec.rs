@@ -0,0 +1,367 @@
+//! A vector in borrowed storage, [`SliceVec`], and its draining iterator.
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut, RangeBounds};
+
+use crate::arrayvec::resolve_range;
+use crate::arrayvec_impl::{ArrayVecImpl, DrainImpl};
+use crate::errors::CapacityError;
+
+/// A vector with the `ArrayVec` API in borrowed, uninitialized storage.
+///
+/// The `SliceVec` is backed by a `&mut [MaybeUninit<T>]` that it does not
+/// own, for example a DMA buffer or memory from an arena. The capacity is the
+/// length of that slice. The first `len` elements are initialized, and they
+/// are dropped when the `SliceVec` is dropped.
+///
+/// It dereferences to a slice, so that the full slice API is available.
+///
+/// ```
+/// use arrayvec::SliceVec;
+/// use std::mem::MaybeUninit;
+///
+/// let mut storage: [MaybeUninit<String>; 4] = [(); 4].map(|_| MaybeUninit::uninit());
+/// let mut vec = SliceVec::new(&mut storage);
+/// vec.push("a".to_string());
+/// vec.push("b".to_string());
+/// vec.insert(0, "c".to_string());
+/// assert_eq!(&vec[..], &["c", "a", "b"]);
+/// assert_eq!(vec.capacity(), 4);
+/// ```
+pub struct SliceVec<'a, T> {
+    // the `len` first elements of the slice are initialized
+    xs: &'a mut [MaybeUninit<T>],
+    len: usize,
+}
+
+impl<'a, T> Drop for SliceVec<'a, T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<'a, T> SliceVec<'a, T> {
+    /// Create a new empty `SliceVec` in the given storage.
+    ///
+    /// The capacity is the length of `storage`. Any values in it are treated
+    /// as uninitialized and are not dropped.
+    ///
+    /// ```
+    /// use arrayvec::SliceVec;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::<u8>::uninit(); 16];
+    /// let vec = SliceVec::new(&mut storage[..8]);
+    /// assert!(vec.is_empty());
+    /// assert_eq!(vec.capacity(), 8);
+    /// ```
+    pub fn new(storage: &'a mut [MaybeUninit<T>]) -> Self {
+        SliceVec { xs: storage, len: 0 }
+    }
+
+    /// Return the number of elements in the `SliceVec`.
+    #[inline]
+    pub fn len(&self) -> usize { self.len }
+
+    /// Returns whether the `SliceVec` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the capacity of the `SliceVec`, the length of its storage.
+    #[inline]
+    pub fn capacity(&self) -> usize { self.xs.len() }
+
+    /// Return true if the `SliceVec` is completely filled to its capacity, false otherwise.
+    pub fn is_full(&self) -> bool { self.len() == self.capacity() }
+
+    /// Returns the capacity left in the `SliceVec`.
+    pub fn remaining_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// ***Panics*** if the vector is already full.
+    #[track_caller]
+    pub fn push(&mut self, element: T) {
+        ArrayVecImpl::push(self, element)
+    }
+
+    /// Push `element` to the end of the vector.
+    ///
+    /// **Errors** if the vector is already full, returning the element.
+    ///
+    /// ```
+    /// use arrayvec::SliceVec;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::uninit(); 1];
+    /// let mut vec = SliceVec::new(&mut storage);
+    /// assert!(vec.try_push(1).is_ok());
+    /// assert_eq!(vec.try_push(2).unwrap_err().element(), 2);
+    /// ```
+    pub fn try_push(&mut self, element: T) -> Result<(), CapacityError<T>> {
+        ArrayVecImpl::try_push(self, element)
+    }
+
+    /// Remove the last element in the vector and return it.
+    ///
+    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    pub fn pop(&mut self) -> Option<T> {
+        ArrayVecImpl::pop(self)
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping
+    /// the rest.
+    ///
+    /// If `len` is greater than the vector’s current length this has no
+    /// effect.
+    pub fn truncate(&mut self, new_len: usize) {
+        ArrayVecImpl::truncate(self, new_len)
+    }
+
+    /// Remove all elements in the vector.
+    pub fn clear(&mut self) {
+        ArrayVecImpl::clear(self)
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// ***Panics*** if the vector is already full or if `index` is out of
+    /// bounds.
+    #[track_caller]
+    pub fn insert(&mut self, index: usize, element: T) {
+        self.try_insert(index, element).unwrap()
+    }
+
+    /// Insert `element` at position `index`.
+    ///
+    /// Shift up all elements after `index`; the `index` must be less than
+    /// or equal to the length.
+    ///
+    /// Returns an error if vector is already at full capacity.
+    ///
+    /// ***Panics*** `index` is out of bounds.
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), CapacityError<T>> {
+        if index > self.len() {
+            panic_oob!("SliceVec", "try_insert", index, self.len())
+        }
+        ArrayVecImpl::try_insert(self, index, element)
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.pop_at(index)
+            .unwrap_or_else(|| {
+                panic_oob!("SliceVec", "remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// This is a checked version of `.remove(index)`. Returns `None` if there
+    /// is no element at `index`. Otherwise, return the element inside `Some`.
+    pub fn pop_at(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::pop_at(self, index)
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        self.swap_pop(index)
+            .unwrap_or_else(|| {
+                panic_oob!("SliceVec", "swap_remove", index, self.len())
+            })
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// This is a checked version of `.swap_remove`.
+    /// Return `Some(` *element* `)` if the index is in bounds, else `None`.
+    pub fn swap_pop(&mut self, index: usize) -> Option<T> {
+        ArrayVecImpl::swap_pop(self, index)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` such that `f(&mut e)` returns false.
+    /// This method operates in place and preserves the order of the retained
+    /// elements.
+    ///
+    /// ```
+    /// use arrayvec::SliceVec;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::uninit(); 4];
+    /// let mut vec = SliceVec::new(&mut storage);
+    /// vec.extend([1, 2, 3, 4].iter().copied());
+    /// vec.retain(|x| *x & 1 != 0 );
+    /// assert_eq!(&vec[..], &[1, 3]);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+        where F: FnMut(&mut T) -> bool
+    {
+        ArrayVecImpl::retain(self, f)
+    }
+
+    /// Copy all elements from the slice and append to the vector.
+    ///
+    /// **Errors** if the capacity left is smaller than the length of the
+    /// slice.
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError>
+        where T: Copy,
+    {
+        ArrayVecImpl::try_extend_from_slice(self, other)
+    }
+
+    /// Create a draining iterator that removes the specified range in the vector
+    /// and yields the removed items from start to end. The element range is
+    /// removed even if the iterator is not consumed until the end.
+    ///
+    /// ***Panics*** if the starting point is greater than the end point or if
+    /// the end point is greater than the length of the vector.
+    ///
+    /// ```
+    /// use arrayvec::SliceVec;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut storage = [MaybeUninit::uninit(); 3];
+    /// let mut vec = SliceVec::new(&mut storage);
+    /// vec.extend([1, 2, 3].iter().copied());
+    /// let drained: Vec<_> = vec.drain(0..2).collect();
+    /// assert_eq!(&vec[..], &[3]);
+    /// assert_eq!(drained, [1, 2]);
+    /// ```
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, 'a, T>
+        where R: RangeBounds<usize>
+    {
+        let (start, end) = resolve_range(range, self.len());
+        Drain { inner: ArrayVecImpl::drain_range(self, start, end) }
+    }
+
+    /// Return a slice containing all elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        ArrayVecImpl::as_slice(self)
+    }
+
+    /// Return a mutable slice containing all elements of the vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        ArrayVecImpl::as_mut_slice(self)
+    }
+
+    /// Return a raw pointer to the vector's buffer.
+    pub fn as_ptr(&self) -> *const T {
+        ArrayVecImpl::as_ptr(self)
+    }
+
+    /// Return a raw mutable pointer to the vector's buffer.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        ArrayVecImpl::as_mut_ptr(self)
+    }
+
+    /// Set the vector’s length without dropping or moving out elements
+    ///
+    /// This method uses *debug assertions* to check that `length` is
+    /// not greater than the capacity.
+    ///
+    /// # Safety
+    ///
+    /// This changes the notion of the number of “valid” elements in the
+    /// vector: the first `length` elements must be initialized.
+    pub unsafe fn set_len(&mut self, length: usize) {
+        ArrayVecImpl::set_len(self, length)
+    }
+}
+
+impl<'a, T> ArrayVecImpl for SliceVec<'a, T> {
+    type Item = T;
+
+    fn capacity(&self) -> usize { self.xs.len() }
+
+    fn len(&self) -> usize { self.len }
+
+    unsafe fn set_len(&mut self, length: usize) {
+        debug_assert!(length <= self.capacity());
+        self.len = length;
+    }
+
+    fn as_ptr(&self) -> *const Self::Item {
+        self.xs.as_ptr() as _
+    }
+
+    fn as_mut_ptr(&mut self) -> *mut Self::Item {
+        self.xs.as_mut_ptr() as _
+    }
+}
+
+impl<'a, T> Deref for SliceVec<'a, T> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<'a, T> DerefMut for SliceVec<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+/// Extend the `SliceVec` with an iterator.
+///
+/// ***Panics*** if extending the vector exceeds its capacity.
+impl<'a, T> Extend<T> for SliceVec<'a, T> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for elt in iter {
+            self.push(elt);
+        }
+    }
+}
+
+impl<'a, T> PartialEq<[T]> for SliceVec<'a, T>
+    where T: PartialEq
+{
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<'a, T> fmt::Debug for SliceVec<'a, T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+/// A draining iterator for `SliceVec`.
+pub struct Drain<'b, 'a, T: 'a> {
+    inner: DrainImpl<'b, SliceVec<'a, T>>,
+}
+
+unsafe impl<'b, 'a, T: Sync> Sync for Drain<'b, 'a, T> {}
+unsafe impl<'b, 'a, T: Send> Send for Drain<'b, 'a, T> {}
+
+impl<'b, 'a, T: 'a> Iterator for Drain<'b, 'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'b, 'a, T: 'a> DoubleEndedIterator for Drain<'b, 'a, T>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'b, 'a, T: 'a> ExactSizeIterator for Drain<'b, 'a, T> {}
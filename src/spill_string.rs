@@ -0,0 +1,306 @@
+
+use alloc::string::String;
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, DerefMut};
+
+use crate::ArrayString;
+
+/// A string that stores up to `CAP` bytes inline, and moves them to a
+/// `String` on the heap when it grows beyond that.
+///
+/// The `SpillString` is the string counterpart of
+/// [`SpillVec`](crate::SpillVec): it starts out as an `ArrayString<CAP>`, and
+/// pushing more than fits *spills* the contents to the heap instead of
+/// failing. Use [`.is_inline()`](SpillString::is_inline) to find out where
+/// the contents are.
+///
+/// It dereferences to `str`.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::SpillString;
+///
+/// let mut s = SpillString::<4>::new();
+/// s.push_str("abc");
+/// assert!(s.is_inline());
+/// s.push_str("def");
+/// assert!(!s.is_inline());
+/// assert_eq!(&s[..], "abcdef");
+/// ```
+pub struct SpillString<const CAP: usize> {
+    data: Data<CAP>,
+}
+
+#[derive(Clone)]
+enum Data<const CAP: usize> {
+    Inline(ArrayString<CAP>),
+    Heap(String),
+}
+
+impl<const CAP: usize> SpillString<CAP> {
+    /// Create a new empty `SpillString`, with its contents inline.
+    ///
+    /// The inline capacity is given by the generic parameter `CAP`.
+    #[track_caller]
+    pub fn new() -> Self {
+        SpillString { data: Data::Inline(ArrayString::new()) }
+    }
+
+    /// Return true if the contents are stored inline, false if they have
+    /// spilled to the heap.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.data, Data::Inline(_))
+    }
+
+    /// Return the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize { self.as_str().len() }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the number of bytes the `SpillString` can hold without
+    /// allocating: `CAP` when inline, or the capacity of the heap string.
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            Data::Inline(_) => CAP,
+            Data::Heap(string) => string.capacity(),
+        }
+    }
+
+    /// Move the contents to the heap, if they are not there yet, with room
+    /// for at least `additional` more bytes.
+    ///
+    /// ***Panics*** like `String::reserve` if the capacity overflows `usize`.
+    fn spill(&mut self, additional: usize) -> &mut String {
+        if let Data::Inline(array) = &self.data {
+            let required = array.len().checked_add(additional).expect("capacity overflow");
+            let mut string = String::with_capacity(cmp::max(required, CAP.saturating_mul(2)));
+            string.push_str(array);
+            self.data = Data::Heap(string);
+        }
+        match &mut self.data {
+            Data::Heap(string) => string,
+            Data::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Adds the given char to the end of the string, spilling to the heap if
+    /// it does not fit inline.
+    pub fn push(&mut self, c: char) {
+        match &mut self.data {
+            Data::Inline(array) => {
+                if array.try_push(c).is_err() {
+                    self.spill(c.len_utf8()).push(c);
+                }
+            }
+            Data::Heap(string) => string.push(c),
+        }
+    }
+
+    /// Adds the given string slice to the end of the string, spilling to the
+    /// heap if it does not fit inline.
+    pub fn push_str(&mut self, s: &str) {
+        match &mut self.data {
+            Data::Inline(array) => {
+                if array.try_push_str(s).is_err() {
+                    self.spill(s.len()).push_str(s);
+                }
+            }
+            Data::Heap(string) => string.push_str(s),
+        }
+    }
+
+    /// Removes the last character from the string and returns it.
+    ///
+    /// Returns `None` if the string is empty.
+    pub fn pop(&mut self) -> Option<char> {
+        match &mut self.data {
+            Data::Inline(array) => array.pop(),
+            Data::Heap(string) => string.pop(),
+        }
+    }
+
+    /// Shortens the string to the specified length.
+    ///
+    /// ***Panics*** if `new_len` does not lie on a `char` boundary.
+    pub fn truncate(&mut self, new_len: usize) {
+        match &mut self.data {
+            Data::Inline(array) => array.truncate(new_len),
+            Data::Heap(string) => string.truncate(new_len),
+        }
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Move the contents inline if they fit, freeing the heap allocation.
+    pub fn shrink_to_inline(&mut self) {
+        if let Data::Heap(string) = &self.data {
+            if let Ok(array) = ArrayString::from(string) {
+                self.data = Data::Inline(array);
+            }
+        }
+    }
+
+    /// Convert into a `String`, which allocates if the contents are inline.
+    pub fn into_string(self) -> String {
+        match self.data {
+            Data::Inline(array) => array.into(),
+            Data::Heap(string) => string,
+        }
+    }
+
+    /// Return a string slice of the whole string.
+    pub fn as_str(&self) -> &str {
+        match &self.data {
+            Data::Inline(array) => array,
+            Data::Heap(string) => string,
+        }
+    }
+
+    /// Return a mutable string slice of the whole string.
+    pub fn as_mut_str(&mut self) -> &mut str {
+        match &mut self.data {
+            Data::Inline(array) => array,
+            Data::Heap(string) => string,
+        }
+    }
+}
+
+impl<const CAP: usize> Deref for SpillString<CAP> {
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const CAP: usize> DerefMut for SpillString<CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<const CAP: usize> Default for SpillString<CAP> {
+    /// Return an empty string
+    fn default() -> Self {
+        SpillString::new()
+    }
+}
+
+impl<const CAP: usize> Clone for SpillString<CAP> {
+    fn clone(&self) -> Self {
+        SpillString { data: self.data.clone() }
+    }
+}
+
+/// Create a `SpillString` from a string slice, inline if it fits.
+impl<'a, const CAP: usize> From<&'a str> for SpillString<CAP> {
+    fn from(s: &'a str) -> Self {
+        let mut string = SpillString::new();
+        string.push_str(s);
+        string
+    }
+}
+
+/// Create an inline `SpillString` from an `ArrayString`.
+impl<const CAP: usize> From<ArrayString<CAP>> for SpillString<CAP> {
+    fn from(s: ArrayString<CAP>) -> Self {
+        SpillString { data: Data::Inline(s) }
+    }
+}
+
+/// Create a `SpillString` from a `String`, keeping the contents on the heap.
+impl<const CAP: usize> From<String> for SpillString<CAP> {
+    fn from(s: String) -> Self {
+        SpillString { data: Data::Heap(s) }
+    }
+}
+
+impl<const CAP: usize> From<SpillString<CAP>> for String {
+    fn from(s: SpillString<CAP>) -> Self {
+        s.into_string()
+    }
+}
+
+impl<const CAP: usize> PartialEq for SpillString<CAP> {
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<const CAP: usize> PartialEq<str> for SpillString<CAP> {
+    fn eq(&self, rhs: &str) -> bool {
+        &**self == rhs
+    }
+}
+
+impl<const CAP: usize> PartialEq<SpillString<CAP>> for str {
+    fn eq(&self, rhs: &SpillString<CAP>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl<const CAP: usize> Eq for SpillString<CAP> { }
+
+impl<const CAP: usize> PartialOrd for SpillString<CAP> {
+    fn partial_cmp(&self, rhs: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<const CAP: usize> Ord for SpillString<CAP> {
+    fn cmp(&self, rhs: &Self) -> cmp::Ordering {
+        (**self).cmp(&**rhs)
+    }
+}
+
+impl<const CAP: usize> Hash for SpillString<CAP> {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        (**self).hash(h)
+    }
+}
+
+impl<const CAP: usize> Borrow<str> for SpillString<CAP> {
+    fn borrow(&self) -> &str { self }
+}
+
+impl<const CAP: usize> BorrowMut<str> for SpillString<CAP> {
+    fn borrow_mut(&mut self) -> &mut str { self }
+}
+
+impl<const CAP: usize> AsRef<str> for SpillString<CAP> {
+    fn as_ref(&self) -> &str { self }
+}
+
+impl<const CAP: usize> fmt::Debug for SpillString<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+impl<const CAP: usize> fmt::Display for SpillString<CAP> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+/// `Write` appends written data to the end of the string, spilling to the
+/// heap if needed.
+impl<const CAP: usize> fmt::Write for SpillString<CAP> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        self.push(c);
+        Ok(())
+    }
+}
@@ -0,0 +1,491 @@
+//! A vector that spills from an array to the heap, [`SpillVec`], and its
+//! iterator.
+//!
+//! Requires `features="alloc"`.
+
+use alloc::vec::{self, Vec};
+use std::borrow::{Borrow, BorrowMut};
+use std::cmp;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+
+use crate::ArrayVec;
+use crate::IntoIter as ArrayIntoIter;
+
+/// A vector that stores up to `CAP` elements inline, and moves them to a
+/// `Vec<T>` on the heap when it grows beyond that.
+///
+/// The `SpillVec` starts out with its elements in an `ArrayVec<T, CAP>`, and
+/// pushing more elements than fit *spills* them to the heap instead of
+/// panicking. It stays on the heap after that, also if elements are removed.
+/// Use [`.is_inline()`](SpillVec::is_inline) to find out where the elements
+/// are.
+///
+/// It dereferences to a slice, so that the full slice API is available.
+///
+/// Requires `features="alloc"`.
+///
+/// ```
+/// use arrayvec::SpillVec;
+///
+/// let mut v = SpillVec::<i32, 2>::new();
+/// v.push(1);
+/// v.push(2);
+/// assert!(v.is_inline());
+/// v.push(3);
+/// assert!(!v.is_inline());
+/// assert_eq!(&v[..], &[1, 2, 3]);
+/// ```
+pub struct SpillVec<T, const CAP: usize> {
+    data: Data<T, CAP>,
+}
+
+enum Data<T, const CAP: usize> {
+    Inline(ArrayVec<T, CAP>),
+    Heap(Vec<T>),
+}
+
+impl<T, const CAP: usize> SpillVec<T, CAP> {
+    /// Create a new empty `SpillVec`, with its elements inline.
+    ///
+    /// The inline capacity is given by the generic parameter `CAP`.
+    ///
+    /// ```
+    /// use arrayvec::SpillVec;
+    ///
+    /// let v = SpillVec::<u8, 16>::new();
+    /// assert!(v.is_inline());
+    /// assert_eq!(v.capacity(), 16);
+    /// ```
+    #[track_caller]
+    pub fn new() -> Self {
+        SpillVec { data: Data::Inline(ArrayVec::new()) }
+    }
+
+    /// Return true if the elements are stored inline, false if they have
+    /// spilled to the heap.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        matches!(self.data, Data::Inline(_))
+    }
+
+    /// Return the number of elements in the `SpillVec`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match &self.data {
+            Data::Inline(array) => array.len(),
+            Data::Heap(vec) => vec.len(),
+        }
+    }
+
+    /// Returns whether the `SpillVec` is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Return the number of elements the `SpillVec` can hold without
+    /// allocating: `CAP` when inline, or the capacity of the heap vector.
+    pub fn capacity(&self) -> usize {
+        match &self.data {
+            Data::Inline(_) => CAP,
+            Data::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    /// Move the elements to the heap, if they are not there yet, with room
+    /// for at least `additional` more.
+    ///
+    /// ***Panics*** like `Vec::reserve` if the capacity overflows `usize`.
+    fn spill(&mut self, additional: usize) -> &mut Vec<T> {
+        if let Data::Inline(array) = &mut self.data {
+            let required = array.len().checked_add(additional).expect("capacity overflow");
+            let mut vec = Vec::with_capacity(cmp::max(required, CAP.saturating_mul(2)));
+            vec.extend(array.drain(..));
+            self.data = Data::Heap(vec);
+        }
+        match &mut self.data {
+            Data::Heap(vec) => vec,
+            Data::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Push `element` to the end of the vector, spilling to the heap if the
+    /// inline array is full.
+    ///
+    /// ```
+    /// use arrayvec::SpillVec;
+    ///
+    /// let mut v = SpillVec::<_, 1>::new();
+    /// v.push("a");
+    /// v.push("b");
+    /// assert_eq!(&v[..], &["a", "b"]);
+    /// ```
+    pub fn push(&mut self, element: T) {
+        match &mut self.data {
+            Data::Inline(array) => {
+                if let Err(err) = array.try_push(element) {
+                    self.spill(1).push(err.element());
+                }
+            }
+            Data::Heap(vec) => vec.push(element),
+        }
+    }
+
+    /// Insert `element` at position `index`, spilling to the heap if the
+    /// inline array is full.
+    ///
+    /// ***Panics*** if `index` is out of bounds.
+    pub fn insert(&mut self, index: usize, element: T) {
+        match &mut self.data {
+            Data::Inline(array) => {
+                if array.is_full() {
+                    self.spill(1).insert(index, element);
+                } else {
+                    array.insert(index, element);
+                }
+            }
+            Data::Heap(vec) => vec.insert(index, element),
+        }
+    }
+
+    /// Remove the last element in the vector and return it.
+    ///
+    /// Return `Some(` *element* `)` if the vector is non-empty, else `None`.
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.data {
+            Data::Inline(array) => array.pop(),
+            Data::Heap(vec) => vec.pop(),
+        }
+    }
+
+    /// Remove the element at `index` and shift down the following elements.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        match &mut self.data {
+            Data::Inline(array) => array.remove(index),
+            Data::Heap(vec) => vec.remove(index),
+        }
+    }
+
+    /// Remove the element at `index` and swap the last element into its place.
+    ///
+    /// ***Panics*** if the `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        match &mut self.data {
+            Data::Inline(array) => array.swap_remove(index),
+            Data::Heap(vec) => vec.swap_remove(index),
+        }
+    }
+
+    /// Shortens the vector, keeping the first `len` elements and dropping
+    /// the rest.
+    pub fn truncate(&mut self, new_len: usize) {
+        match &mut self.data {
+            Data::Inline(array) => array.truncate(new_len),
+            Data::Heap(vec) => vec.truncate(new_len),
+        }
+    }
+
+    /// Remove all elements in the vector.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Retains only the elements specified by the predicate.
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&mut T) -> bool
+    {
+        match &mut self.data {
+            Data::Inline(array) => array.retain(f),
+            Data::Heap(vec) => vec.retain_mut(|x| f(x)),
+        }
+    }
+
+    /// Clone and append all elements of the slice, spilling to the heap if
+    /// they do not fit inline.
+    ///
+    /// ```
+    /// use arrayvec::SpillVec;
+    ///
+    /// let mut v = SpillVec::<_, 4>::new();
+    /// v.extend_from_slice(&[1, 2, 3]);
+    /// assert!(v.is_inline());
+    /// v.extend_from_slice(&[4, 5]);
+    /// assert!(!v.is_inline());
+    /// ```
+    pub fn extend_from_slice(&mut self, other: &[T])
+        where T: Clone,
+    {
+        match &mut self.data {
+            Data::Inline(array) if other.len() <= array.remaining_capacity() => {
+                array.extend(other.iter().cloned())
+            }
+            _ => self.spill(other.len()).extend_from_slice(other),
+        }
+    }
+
+    /// Move the elements inline if they fit, freeing the heap allocation.
+    ///
+    /// ```
+    /// use arrayvec::SpillVec;
+    ///
+    /// let mut v: SpillVec<_, 2> = (0..3).collect();
+    /// assert!(!v.is_inline());
+    /// v.pop();
+    /// v.shrink_to_inline();
+    /// assert!(v.is_inline());
+    /// ```
+    pub fn shrink_to_inline(&mut self) {
+        if let Data::Heap(vec) = &mut self.data {
+            if vec.len() <= CAP {
+                let array = vec.drain(..).collect();
+                self.data = Data::Inline(array);
+            }
+        }
+    }
+
+    /// Convert into a `Vec<T>`, which allocates if the elements are inline.
+    pub fn into_vec(self) -> Vec<T> {
+        match self.data {
+            Data::Inline(array) => array.into(),
+            Data::Heap(vec) => vec,
+        }
+    }
+
+    /// Return a slice containing all elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        match &self.data {
+            Data::Inline(array) => array,
+            Data::Heap(vec) => vec,
+        }
+    }
+
+    /// Return a mutable slice containing all elements of the vector.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        match &mut self.data {
+            Data::Inline(array) => array,
+            Data::Heap(vec) => vec,
+        }
+    }
+}
+
+impl<T, const CAP: usize> Deref for SpillVec<T, CAP> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const CAP: usize> DerefMut for SpillVec<T, CAP> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const CAP: usize> Default for SpillVec<T, CAP> {
+    /// Return an empty vector
+    fn default() -> Self {
+        SpillVec::new()
+    }
+}
+
+/// Create an inline `SpillVec` from an `ArrayVec`.
+impl<T, const CAP: usize> From<ArrayVec<T, CAP>> for SpillVec<T, CAP> {
+    fn from(array: ArrayVec<T, CAP>) -> Self {
+        SpillVec { data: Data::Inline(array) }
+    }
+}
+
+/// Create a `SpillVec` from a `Vec`, keeping the elements on the heap.
+impl<T, const CAP: usize> From<Vec<T>> for SpillVec<T, CAP> {
+    fn from(vec: Vec<T>) -> Self {
+        SpillVec { data: Data::Heap(vec) }
+    }
+}
+
+impl<T, const CAP: usize> From<SpillVec<T, CAP>> for Vec<T> {
+    fn from(v: SpillVec<T, CAP>) -> Self {
+        v.into_vec()
+    }
+}
+
+/// Extend the `SpillVec` with an iterator, spilling to the heap if the
+/// elements do not fit inline.
+impl<T, const CAP: usize> Extend<T> for SpillVec<T, CAP> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        if let Data::Inline(array) = &mut self.data {
+            while !array.is_full() {
+                match iter.next() {
+                    Some(elt) => array.push(elt),
+                    None => return,
+                }
+            }
+        }
+        let mut iter = iter.peekable();
+        if iter.peek().is_some() {
+            self.spill(iter.size_hint().0).extend(iter);
+        }
+    }
+}
+
+/// Create a `SpillVec` from an iterator, spilling to the heap if the elements
+/// do not fit inline.
+impl<T, const CAP: usize> iter::FromIterator<T> for SpillVec<T, CAP> {
+    fn from_iter<I: IntoIterator<Item=T>>(iter: I) -> Self {
+        let mut v = SpillVec::new();
+        v.extend(iter);
+        v
+    }
+}
+
+impl<T, const CAP: usize> Clone for SpillVec<T, CAP>
+    where T: Clone
+{
+    fn clone(&self) -> Self {
+        let data = match &self.data {
+            Data::Inline(array) => Data::Inline(array.clone()),
+            Data::Heap(vec) => Data::Heap(vec.clone()),
+        };
+        SpillVec { data }
+    }
+}
+
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a SpillVec<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+impl<'a, T: 'a, const CAP: usize> IntoIterator for &'a mut SpillVec<T, CAP> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+}
+
+/// Iterate the `SpillVec` with each element by value.
+impl<T, const CAP: usize> IntoIterator for SpillVec<T, CAP> {
+    type Item = T;
+    type IntoIter = IntoIter<T, CAP>;
+    fn into_iter(self) -> IntoIter<T, CAP> {
+        let iter = match self.data {
+            Data::Inline(array) => IntoIterData::Inline(array.into_iter()),
+            Data::Heap(vec) => IntoIterData::Heap(vec.into_iter()),
+        };
+        IntoIter { iter }
+    }
+}
+
+impl<T, const CAP: usize> PartialEq for SpillVec<T, CAP>
+    where T: PartialEq
+{
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T, const CAP: usize> PartialEq<[T]> for SpillVec<T, CAP>
+    where T: PartialEq
+{
+    fn eq(&self, other: &[T]) -> bool {
+        **self == *other
+    }
+}
+
+impl<T, const CAP: usize> Eq for SpillVec<T, CAP> where T: Eq { }
+
+impl<T, const CAP: usize> PartialOrd for SpillVec<T, CAP> where T: PartialOrd {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T, const CAP: usize> Ord for SpillVec<T, CAP> where T: Ord {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T, const CAP: usize> Hash for SpillVec<T, CAP>
+    where T: Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(&**self, state)
+    }
+}
+
+impl<T, const CAP: usize> Borrow<[T]> for SpillVec<T, CAP> {
+    fn borrow(&self) -> &[T] { self }
+}
+
+impl<T, const CAP: usize> BorrowMut<[T]> for SpillVec<T, CAP> {
+    fn borrow_mut(&mut self) -> &mut [T] { self }
+}
+
+impl<T, const CAP: usize> AsRef<[T]> for SpillVec<T, CAP> {
+    fn as_ref(&self) -> &[T] { self }
+}
+
+impl<T, const CAP: usize> AsMut<[T]> for SpillVec<T, CAP> {
+    fn as_mut(&mut self) -> &mut [T] { self }
+}
+
+impl<T, const CAP: usize> fmt::Debug for SpillVec<T, CAP> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { (**self).fmt(f) }
+}
+
+/// By-value iterator for `SpillVec`.
+pub struct IntoIter<T, const CAP: usize> {
+    iter: IntoIterData<T, CAP>,
+}
+
+enum IntoIterData<T, const CAP: usize> {
+    Inline(ArrayIntoIter<T, CAP>),
+    Heap(vec::IntoIter<T>),
+}
+
+impl<T, const CAP: usize> Iterator for IntoIter<T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match &mut self.iter {
+            IntoIterData::Inline(iter) => iter.next(),
+            IntoIterData::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.iter {
+            IntoIterData::Inline(iter) => iter.size_hint(),
+            IntoIterData::Heap(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<T, const CAP: usize> DoubleEndedIterator for IntoIter<T, CAP> {
+    fn next_back(&mut self) -> Option<T> {
+        match &mut self.iter {
+            IntoIterData::Inline(iter) => iter.next_back(),
+            IntoIterData::Heap(iter) => iter.next_back(),
+        }
+    }
+}
+
+impl<T, const CAP: usize> ExactSizeIterator for IntoIter<T, CAP> { }
+
+impl<T, const CAP: usize> fmt::Debug for IntoIter<T, CAP>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.iter {
+            IntoIterData::Inline(iter) => iter.fmt(f),
+            IntoIterData::Heap(iter) => f.debug_list().entries(iter.as_slice()).finish(),
+        }
+    }
+}
@@ -9,3 +9,11 @@ impl<T, const N: usize> MakeMaybeUninit<T, N> {
     pub(crate) const ARRAY: [MaybeUninit<T>; N] = [Self::VALUE; N];
 }
 
+
+/// Compile time check that a capacity `FROM` fits in a capacity `TO`.
+pub(crate) struct CapacityFits<const FROM: usize, const TO: usize>;
+
+impl<const FROM: usize, const TO: usize> CapacityFits<FROM, TO> {
+    /// Evaluating this constant fails to compile if `FROM > TO`.
+    pub(crate) const ASSERT: () = [()][(FROM > TO) as usize];
+}
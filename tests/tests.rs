@@ -2,6 +2,7 @@ extern crate arrayvec;
 #[macro_use] extern crate matches;
 
 use arrayvec::ArrayVec;
+use arrayvec::ArrayDeque;
 use arrayvec::ArrayString;
 use std::mem;
 use arrayvec::CapacityError;
@@ -790,4 +791,1298 @@ fn test_arraystring_zero_filled_has_some_sanity_checks() {
     let string = ArrayString::<4>::zero_filled();
     assert_eq!(string.as_str(), "\0\0\0\0");
     assert_eq!(string.len(), 4);
-}
\ No newline at end of file
+}
+#[test]
+fn test_arraydeque_push_pop() {
+    let mut deque = ArrayDeque::<i32, 4>::new();
+    assert_eq!(deque.pop_front(), None);
+    assert_eq!(deque.pop_back(), None);
+
+    deque.push_back(2);
+    deque.push_back(3);
+    deque.push_front(1);
+    deque.push_front(0);
+    assert!(deque.is_full());
+    assert_matches!(deque.try_push_back(4), Err(_));
+    assert_matches!(deque.try_push_front(-1), Err(_));
+    assert!(deque.iter().eq(&[0, 1, 2, 3]));
+    assert!(deque.iter().rev().eq(&[3, 2, 1, 0]));
+    assert_eq!(deque[1], 1);
+
+    // walk the elements around the ring a few times
+    for i in 4..20 {
+        assert_eq!(deque.pop_front(), Some(i - 4));
+        deque.push_back(i);
+        assert!(deque.iter().copied().eq(i - 3..i + 1));
+        let (a, b) = deque.as_slices();
+        assert_eq!(a.len() + b.len(), 4);
+    }
+    assert_eq!(deque.pop_back(), Some(19));
+    assert_eq!(deque.pop_front(), Some(16));
+    assert_eq!(deque.len(), 2);
+}
+
+#[test]
+fn test_arraydeque_make_contiguous() {
+    let mut deque = ArrayDeque::<String, 5>::new();
+    for i in 0..3 {
+        deque.push_back(i.to_string());
+    }
+    deque.push_front("a".to_string());
+    deque.push_front("b".to_string());
+    assert!(!deque.as_slices().1.is_empty());
+
+    assert_eq!(deque.make_contiguous(), &["b", "a", "0", "1", "2"]);
+    assert!(deque.as_slices().1.is_empty());
+    deque.pop_front();
+    deque.push_back("3".to_string());
+    assert_eq!(deque.make_contiguous(), &["a", "0", "1", "2", "3"]);
+}
+
+#[test]
+fn test_arraydeque_drain() {
+    // Try every range in every rotation of the ring
+    for rotation in 0..8 {
+        for start in 0..=8 {
+            for end in start..=8 {
+                let mut deque = ArrayDeque::<String, 8>::new();
+                for _ in 0..rotation {
+                    deque.push_back(String::new());
+                    deque.pop_front();
+                }
+                deque.extend((0..8).map(|i| i.to_string()));
+
+                let drained: Vec<_> = deque.drain(start..end).collect();
+                let expected: Vec<_> = (start..end).map(|i| i.to_string()).collect();
+                assert_eq!(drained, expected);
+                let rest: Vec<_> = (0..start).chain(end..8).map(|i| i.to_string()).collect();
+                assert!(deque.iter().eq(&rest));
+            }
+        }
+    }
+
+    let mut deque = ArrayDeque::from([1, 2, 3, 4, 5]);
+    {
+        let mut drain = deque.drain(1..4);
+        assert_eq!(drain.next_back(), Some(4));
+        assert_eq!(drain.next(), Some(2));
+    }
+    assert!(deque.iter().eq(&[1, 5]));
+}
+
+#[should_panic]
+#[test]
+fn test_arraydeque_drain_oob() {
+    let mut deque = ArrayDeque::from([1, 2, 3]);
+    deque.drain(0..4);
+}
+
+#[test]
+fn test_arraydeque_drop() {
+    use std::cell::Cell;
+
+    let flag = &Cell::new(0);
+
+    #[derive(Clone)]
+    struct Bump<'a>(&'a Cell<i32>);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    {
+        let mut deque = ArrayDeque::<Bump, 4>::new();
+        deque.push_back(Bump(flag));
+        deque.push_back(Bump(flag));
+        deque.pop_front();
+        assert_eq!(flag.get(), 1);
+        deque.push_back(Bump(flag));
+        deque.push_back(Bump(flag));
+        deque.push_back(Bump(flag));
+        deque.truncate(2);
+        assert_eq!(flag.get(), 3);
+        deque.push_front(Bump(flag));
+        deque.drain(1..2);
+        assert_eq!(flag.get(), 4);
+    }
+    assert_eq!(flag.get(), 6);
+
+    flag.set(0);
+    {
+        let mut deque = ArrayDeque::<Bump, 3>::new();
+        deque.push_back(Bump(flag));
+        deque.push_front(Bump(flag));
+        deque.push_front(Bump(flag));
+        let mut iter = deque.into_iter();
+        iter.next();
+        assert_eq!(flag.get(), 1);
+        let clone = iter.clone();
+        drop(clone);
+        assert_eq!(flag.get(), 3);
+    }
+    assert_eq!(flag.get(), 5);
+}
+
+#[test]
+fn test_arraydeque_eq_hash() {
+    let mut a = ArrayDeque::<i32, 4>::new();
+    a.push_back(2);
+    a.push_front(1);
+    let b: ArrayDeque<i32, 4> = (1..3).collect();
+    assert_eq!(a, b);
+    assert_eq!(format!("{:?}", a), "[1, 2]");
+
+    let mut map = HashMap::new();
+    map.insert(a, "a");
+    assert_eq!(map.get(&b), Some(&"a"));
+}
+
+#[test]
+fn test_arraydeque_zst() {
+    let mut deque = ArrayDeque::<(), 3>::new();
+    deque.push_back(());
+    deque.push_front(());
+    deque.push_front(());
+    assert_matches!(deque.try_push_back(()), Err(_));
+    assert_eq!(deque.drain(1..).count(), 2);
+    assert_eq!(deque.len(), 1);
+    assert_eq!(deque.into_iter().count(), 1);
+}
+
+#[test]
+fn test_arraydeque_push_overwrite() {
+    let mut history = ArrayDeque::<String, 3>::new();
+    for i in 0..3 {
+        assert_eq!(history.push_back_overwrite(i.to_string()), None);
+    }
+    for i in 3..10 {
+        let evicted = history.push_back_overwrite(i.to_string());
+        assert_eq!(evicted, Some((i - 3).to_string()));
+        assert!(history.iter().eq(&[(i - 2).to_string(), (i - 1).to_string(), i.to_string()]));
+    }
+    assert_eq!(history.push_front_overwrite("6".to_string()), Some("9".to_string()));
+    assert!(history.iter().eq(&["6", "7", "8"]));
+    assert!(history.into_iter().rev().eq(["8", "7", "6"]));
+
+    let mut empty = ArrayDeque::<i32, 0>::new();
+    assert_eq!(empty.push_back_overwrite(1), Some(1));
+    assert_eq!(empty.push_front_overwrite(2), Some(2));
+}
+
+#[test]
+fn test_splice() {
+    let mut v: ArrayVec<_, 6> = (0..4).collect();
+    let removed: Vec<_> = v.splice(1..3, 10..13).collect();
+    assert_eq!(removed, [1, 2]);
+    assert_eq!(&v[..], &[0, 10, 11, 12, 3]);
+
+    // shrink, and not consuming the removed elements
+    v.splice(..4, Some(20));
+    assert_eq!(&v[..], &[20, 3]);
+
+    // grow to exactly the capacity with an iterator without a size hint
+    v.splice(1..1, (0..10).filter(|&x| x < 4));
+    assert_eq!(&v[..], &[20, 0, 1, 2, 3, 3]);
+
+    v.splice(.., None);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_splice_capacity_panic() {
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let mut v: ArrayVec<String, 4> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        v.splice(1..2, ["x", "y", "z"].iter().map(|s| s.to_string()));
+    }));
+    assert!(res.is_err());
+    // the replacement is truncated at the capacity, and the tail is kept
+    assert_eq!(&v[..], &["a", "x", "y", "c"]);
+}
+
+#[test]
+fn test_try_splice() {
+    let mut v = ArrayVec::<_, 4>::new();
+    v.extend(["a", "b", "c"].iter().map(|s| s.to_string()));
+
+    let removed: Vec<_> = v.try_splice(0..1, vec!["x".to_string(), "y".to_string()])
+        .unwrap()
+        .collect();
+    assert_eq!(removed, ["a"]);
+    assert_eq!(&v[..], &["x", "y", "b", "c"]);
+
+    let err = v.try_splice(1..1, vec!["z".to_string()]).err().unwrap();
+    assert_eq!(err.element().collect::<Vec<_>>(), ["z"]);
+    assert_eq!(&v[..], &["x", "y", "b", "c"]);
+
+    assert!(v.try_splice(.., Vec::new()).is_ok());
+    assert!(v.is_empty());
+}
+
+#[should_panic]
+#[test]
+fn test_try_splice_oob() {
+    let mut v = ArrayVec::<i32, 4>::new();
+    let _ = v.try_splice(0..1, Some(1));
+}
+
+#[test]
+fn test_extract_if() {
+    let mut v: ArrayVec<_, 10> = (0..10).map(|i| i.to_string()).collect();
+    let extracted: Vec<_> = v.extract_if(2..8, |s| s.parse::<i32>().unwrap() % 3 == 0).collect();
+    assert_eq!(extracted, ["3", "6"]);
+    assert_eq!(&v[..], &["0", "1", "2", "4", "5", "7", "8", "9"]);
+
+    // dropping early retains the rest
+    let mut v: ArrayVec<_, 10> = (0..10).collect();
+    {
+        let mut iter = v.extract_if(.., |x| *x % 2 == 0);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(2));
+    }
+    assert_eq!(&v[..], &[1, 3, 4, 5, 6, 7, 8, 9]);
+
+    // elements can be modified by the predicate
+    let mut v = ArrayVec::from([1, 2, 3]);
+    assert_eq!(v.extract_if(.., |x| { *x *= 10; *x > 10 }).count(), 2);
+    assert_eq!(&v[..], &[10]);
+}
+
+#[test]
+fn test_extract_if_panic() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v: ArrayVec<_, 8> = (0..8).map(|i| Bump(flag, i)).collect();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        for _ in v.extract_if(.., |b| {
+            if b.1 == 5 {
+                panic!("panic in extract_if predicate");
+            }
+            b.1 % 2 == 0
+        }) { }
+    }));
+    assert!(res.is_err());
+    // 0, 2, 4 were extracted and dropped, the rest is retained in order
+    assert_eq!(flag.get(), 3);
+    assert_eq!(v.iter().map(|b| b.1).collect::<Vec<_>>(), [1, 3, 5, 6, 7]);
+    drop(v);
+    assert_eq!(flag.get(), 8);
+}
+
+#[test]
+fn test_dedup() {
+    let mut v = ArrayVec::<i32, 8>::new();
+    v.dedup();
+    assert!(v.is_empty());
+
+    v.extend([1, 1, 2, 3, 3, 3, 1, 1].iter().copied());
+    v.dedup();
+    assert_eq!(&v[..], &[1, 2, 3, 1]);
+
+    let mut v: ArrayVec<_, 6> = ["a", "A", "b", "B", "b", "c"].iter().map(|s| s.to_string()).collect();
+    v.dedup_by_key(|s| s.to_ascii_lowercase());
+    assert_eq!(&v[..], &["a", "b", "c"]);
+
+    let mut v = ArrayVec::from([1, 2, 4, 5, 7, 8]);
+    v.dedup_by(|a, b| *a == *b + 1);
+    assert_eq!(&v[..], &[1, 4, 7]);
+}
+
+#[test]
+fn test_dedup_panic() {
+    use std::cell::Cell;
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let flag = &Cell::new(0);
+
+    struct Bump<'a>(&'a Cell<i32>, i32);
+
+    impl<'a> Drop for Bump<'a> {
+        fn drop(&mut self) {
+            let n = self.0.get();
+            self.0.set(n + 1);
+        }
+    }
+
+    let mut v: ArrayVec<_, 8> = [0, 0, 1, 1, 2, 2, 3, 3].iter().map(|&i| Bump(flag, i)).collect();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        v.dedup_by(|a, b| {
+            if a.1 == 2 {
+                panic!("panic in dedup_by");
+            }
+            a.1 == b.1
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(flag.get(), 2);
+    assert_eq!(v.iter().map(|b| b.1).collect::<Vec<_>>(), [0, 1, 2, 2, 3, 3]);
+    drop(v);
+    assert_eq!(flag.get(), 8);
+}
+
+#[test]
+fn test_resize() {
+    let mut v = ArrayVec::<String, 4>::new();
+    v.resize(3, "a".to_string());
+    assert_eq!(&v[..], &["a", "a", "a"]);
+    v.resize(1, "b".to_string());
+    assert_eq!(&v[..], &["a"]);
+    v.resize(4, "c".to_string());
+    assert_eq!(&v[..], &["a", "c", "c", "c"]);
+    v.resize(4, "d".to_string());
+    assert_eq!(&v[..], &["a", "c", "c", "c"]);
+
+    let err = v.try_resize(5, "e".to_string()).unwrap_err();
+    assert_eq!(err.element(), "e");
+    assert_eq!(v.len(), 4);
+    assert!(v.try_resize(0, "f".to_string()).is_ok());
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_resize_with() {
+    let mut v = ArrayVec::<_, 4>::new();
+    let mut calls = 0;
+    v.resize_with(3, || { calls += 1; calls });
+    assert_eq!(&v[..], &[1, 2, 3]);
+
+    assert!(v.try_resize_with(5, || unreachable!()).is_err());
+    assert_eq!(&v[..], &[1, 2, 3]);
+    v.resize_with(1, || unreachable!());
+    assert_eq!(&v[..], &[1]);
+}
+
+#[should_panic]
+#[test]
+fn test_resize_capacity_panic() {
+    let mut v = ArrayVec::<i32, 4>::new();
+    v.resize(5, 0);
+}
+
+#[test]
+fn test_append() {
+    let mut v1 = ArrayVec::<String, 4>::new();
+    v1.push("a".to_string());
+    let mut v2: ArrayVec<_, 8> = ["b", "c"].iter().map(|s| s.to_string()).collect();
+    v1.append(&mut v2);
+    assert_eq!(&v1[..], &["a", "b", "c"]);
+    assert!(v2.is_empty());
+
+    v2.extend(["d", "e"].iter().map(|s| s.to_string()));
+    assert!(v1.try_append(&mut v2).is_err());
+    assert_eq!(v1.len(), 3);
+    assert_eq!(&v2[..], &["d", "e"]);
+
+    let mut empty = ArrayVec::<String, 0>::new();
+    v1.append(&mut empty);
+    assert_eq!(v1.len(), 3);
+}
+
+#[test]
+fn test_split_off() {
+    let mut v1: ArrayVec<_, 6> = (0..6).map(|i| i.to_string()).collect();
+    let v2: ArrayVec<_, 6> = v1.split_off(4);
+    assert_eq!(&v1[..], &["0", "1", "2", "3"]);
+    assert_eq!(&v2[..], &["4", "5"]);
+
+    assert!(v1.try_split_off::<2>(1).is_err());
+    assert_eq!(v1.len(), 4);
+
+    let v3: ArrayVec<_, 0> = v1.split_off(4);
+    assert!(v3.is_empty());
+    let v4: ArrayVec<_, 4> = v1.split_off(0);
+    assert!(v1.is_empty());
+    assert_eq!(&v4[..], &["0", "1", "2", "3"]);
+}
+
+#[should_panic]
+#[test]
+fn test_split_off_oob() {
+    let mut v = ArrayVec::from([1, 2, 3]);
+    let _: ArrayVec<_, 3> = v.split_off(4);
+}
+
+#[test]
+fn test_into_capacity() {
+    let v: ArrayVec<_, 3> = (0..3).map(|i| i.to_string()).collect();
+    let w: ArrayVec<_, 5> = v.into_capacity();
+    assert_eq!(&w[..], &["0", "1", "2"]);
+    assert_eq!(w.capacity(), 5);
+
+    let w = w.try_into_capacity::<2>().unwrap_err();
+    assert_eq!(&w[..], &["0", "1", "2"]);
+    let v = w.try_into_capacity::<3>().unwrap();
+    assert_eq!(&v[..], &["0", "1", "2"]);
+    assert!(v.is_full());
+
+    let s = ArrayString::<4>::from("abc").unwrap();
+    let t: ArrayString<8> = s.into_capacity();
+    assert_eq!(t, *"abc");
+    assert_eq!(t.try_into_capacity::<2>().unwrap_err(), t);
+    assert_eq!(t.try_into_capacity::<3>().unwrap(), *"abc");
+}
+
+#[test]
+fn test_arrayvec_macro() {
+    use arrayvec::arrayvec;
+
+    let v: ArrayVec<i32, 3> = arrayvec![];
+    assert!(v.is_empty());
+    let v = arrayvec![3 =>];
+    assert_eq!(v.capacity(), 3);
+    assert_eq!(v, ArrayVec::<u8, 3>::new());
+
+    let v: ArrayVec<_, 4> = arrayvec!["a".to_string(), "b".to_string(),];
+    assert_eq!(&v[..], &["a", "b"]);
+    let v: ArrayVec<_, 2> = arrayvec![1, 2];
+    assert!(v.is_full());
+
+    const CAP: usize = 5;
+    let v = arrayvec![CAP => 1, 2, 3];
+    assert_eq!(&v[..], &[1, 2, 3]);
+    assert_eq!(v.capacity(), CAP);
+
+    let v: ArrayVec<_, 4> = arrayvec![vec![1]; 4];
+    assert_eq!(&v[..], &[vec![1], vec![1], vec![1], vec![1]]);
+    let n = 2;
+    let v = arrayvec![CAP => 'x'; n];
+    assert_eq!(&v[..], &['x', 'x']);
+}
+
+#[should_panic]
+#[test]
+fn test_arrayvec_macro_repeat_panic() {
+    use arrayvec::arrayvec;
+
+    let n = 5;
+    let _ = arrayvec![4 => 0; n];
+}
+
+#[test]
+fn test_array_string_macro() {
+    use arrayvec::array_string;
+
+    let s: ArrayString<4> = array_string!();
+    assert!(s.is_empty());
+    let s = array_string!(3 =>);
+    assert_eq!(s.capacity(), 3);
+
+    let s: ArrayString<4> = array_string!("abcd");
+    assert_eq!(&s, "abcd");
+    let owned = String::from("xyz");
+    let s = array_string!(5 => &owned);
+    assert_eq!(&s, "xyz");
+    assert_eq!(s.capacity(), 5);
+}
+
+#[should_panic]
+#[test]
+fn test_array_string_macro_panic() {
+    use arrayvec::array_string;
+
+    let _ = array_string!(2 => "abc");
+}
+
+#[test]
+fn test_array_format() {
+    use arrayvec::array_format;
+
+    let s = array_format!(11, "{} {}", "hello", 1234).unwrap();
+    assert_eq!(&s, "hello 1234");
+    assert_eq!(s.capacity(), 11);
+    assert_eq!(array_format!(10, "{} {}", "hello", 12345), Err(CapacityError::new(())));
+}
+
+#[test]
+fn test_arraystring_truncating() {
+    use std::fmt::Write;
+
+    let mut s = ArrayString::<8>::new();
+    {
+        let mut w = s.truncating();
+        write!(w, "{}", 1234).unwrap();
+        assert!(!w.is_truncated());
+        // "é" is two bytes and is not split
+        w.write_str("abcé").unwrap();
+        assert!(w.is_truncated());
+        write!(w, "more").unwrap();
+    }
+    assert_eq!(&s, "1234abc");
+
+    // the marker replaces the end of the text
+    let mut s = ArrayString::<8>::new();
+    {
+        let mut w = s.truncating_with("...");
+        w.write_str("abcdef").unwrap();
+        w.write_str("gh").unwrap();
+        assert!(!w.is_truncated());
+        w.write_char('i').unwrap();
+        assert!(w.is_truncated());
+    }
+    assert_eq!(&s, "abcde...");
+
+    // the marker is truncated if it is longer than the capacity
+    let mut s = ArrayString::<2>::new();
+    assert!(s.truncating_with("αβ").write_str("abc").is_ok());
+    assert_eq!(&s, "α");
+
+    let mut s = ArrayString::<5>::new();
+    assert!(!s.write_truncated(format_args!("{}", "αβ")));
+    assert!(s.write_truncated(format_args!("{}", "γ")));
+    assert_eq!(&s, "αβ");
+}
+
+#[test]
+fn test_arraystring_extend() {
+    let mut s = ArrayString::<16>::new();
+    s.extend("ab".chars());
+    s.extend(['c', 'd'].iter());
+    s.extend(["ef", "", "g"].iter().copied());
+    s.extend(Some(ArrayString::<4>::from("hi").unwrap()));
+    assert_eq!(&s, "abcdefghi");
+
+    let s: ArrayString<8> = "a-b-c".split('-').collect();
+    assert_eq!(&s, "abc");
+    let s: ArrayString<8> = "αβ".chars().rev().collect();
+    assert_eq!(&s, "βα");
+
+    let mut s = ArrayString::<3>::new();
+    assert_eq!(s.try_extend("abαβ".chars()), Err(CapacityError::new('α')));
+    assert_eq!(&s, "ab");
+    let err = ArrayString::<3>::try_from_iter(["a", "bcd"].iter().copied()).unwrap_err();
+    assert_eq!(err.element(), "bcd");
+}
+
+#[should_panic(expected = "capacity exceeded")]
+#[test]
+fn test_arraystring_extend_capacity_panic() {
+    let _: ArrayString<3> = "abcd".chars().collect();
+}
+
+#[test]
+fn test_arraystring_insert() {
+    let mut s = ArrayString::<8>::from("αc").unwrap();
+    s.insert(2, 'b');
+    s.insert_str(0, "<");
+    s.insert_str(s.len(), ">");
+    assert_eq!(&s, "<αbc>");
+    assert_eq!(s.try_insert_str(1, "xyz"), Err(CapacityError::new("xyz")));
+    s.try_insert(1, 'β').unwrap();
+    assert_eq!(&s, "<βαbc>");
+    assert_eq!(s.try_insert(1, 'x'), Err(CapacityError::new('x')));
+}
+
+#[should_panic(expected = "ArrayString::insert: index 1 is not a char boundary in string of length 2")]
+#[test]
+fn test_arraystring_insert_not_char_boundary() {
+    let mut s = ArrayString::<8>::from("α").unwrap();
+    s.insert(1, 'x');
+}
+
+#[should_panic(expected = "ArrayString::insert_str: index 1 is not a char boundary in string of length 2")]
+#[test]
+fn test_arraystring_insert_str_not_char_boundary() {
+    let mut s = ArrayString::<8>::from("α").unwrap();
+    s.insert_str(1, "x");
+}
+
+#[should_panic]
+#[test]
+fn test_arraystring_insert_oob() {
+    let mut s = ArrayString::<8>::from("a").unwrap();
+    s.insert_str(2, "x");
+}
+
+#[test]
+fn test_arraystring_retain() {
+    let mut s = ArrayString::<16>::from("αaβbγc").unwrap();
+    s.retain(|c| !c.is_ascii());
+    assert_eq!(&s, "αβγ");
+
+    use std::panic::catch_unwind;
+    use std::panic::AssertUnwindSafe;
+
+    let mut s = ArrayString::<16>::from("a_b_c_d").unwrap();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        s.retain(|c| {
+            if c == 'c' {
+                panic!("panic in retain");
+            }
+            c != '_'
+        });
+    }));
+    assert!(res.is_err());
+    // the string is still valid, but cut off at the panic
+    assert_eq!(&s, "ab");
+}
+
+#[test]
+fn test_arraystring_drain() {
+    let mut s = ArrayString::<16>::from("abαβγcd").unwrap();
+    {
+        let mut drain = s.drain(2..8);
+        assert_eq!(drain.next(), Some('α'));
+        assert_eq!(drain.next_back(), Some('γ'));
+        assert_eq!(drain.as_str(), "β");
+    }
+    assert_eq!(&s, "abcd");
+    assert_eq!(s.drain(..).collect::<String>(), "abcd");
+    assert!(s.is_empty());
+}
+
+#[should_panic]
+#[test]
+fn test_arraystring_drain_not_char_boundary() {
+    let mut s = ArrayString::<16>::from("abα").unwrap();
+    s.drain(..3);
+}
+
+#[test]
+fn test_arraystring_replace_range() {
+    let mut s = ArrayString::<8>::from("abcdef").unwrap();
+    s.replace_range(1..5, "X");
+    assert_eq!(&s, "aXf");
+    s.replace_range(1..=1, "αβγ");
+    assert_eq!(&s, "aαβγf");
+    assert_eq!(s.try_replace_range(..1, "xy"), Err(CapacityError::new("xy")));
+    s.try_replace_range(..1, "x").unwrap();
+    assert_eq!(&s, "xαβγf");
+    s.replace_range(.., "");
+    assert!(s.is_empty());
+}
+
+#[test]
+fn test_arraystring_split_off() {
+    let mut s = ArrayString::<8>::from("abcαβ").unwrap();
+    let t: ArrayString<4> = s.split_off(3);
+    assert_eq!(&s, "abc");
+    assert_eq!(&t, "αβ");
+
+    assert!(s.try_split_off::<1>(1).is_err());
+    assert_eq!(&s, "abc");
+    let t: ArrayString<0> = s.split_off(3);
+    assert_eq!(&t, "");
+}
+
+#[should_panic]
+#[test]
+fn test_arraystring_split_off_not_char_boundary() {
+    let mut s = ArrayString::<8>::from("α").unwrap();
+    let _: ArrayString<8> = s.split_off(1);
+}
+
+#[test]
+fn test_arraystring_case_mapping() {
+    let mut s = ArrayString::<16>::from("Hello, Wörld").unwrap();
+    s.make_ascii_uppercase();
+    assert_eq!(&s, "HELLO, WöRLD");
+    s.make_ascii_lowercase();
+    assert_eq!(&s, "hello, wörld");
+
+    let s = ArrayString::<16>::from("ΑΒΓ ﬁ ß").unwrap();
+    assert_eq!(s.to_lowercase::<16>().unwrap(), *"αβγ ﬁ ß");
+    assert_eq!(s.to_uppercase::<16>().unwrap(), *"ΑΒΓ FI SS");
+    assert!(s.to_uppercase::<12>().is_ok());
+    assert!(s.to_uppercase::<11>().is_err());
+}
+
+#[test]
+fn test_arraystring_replace_repeat() {
+    let s = ArrayString::<8>::from("a.b.c").unwrap();
+    assert_eq!(s.replace::<8>(".", "::").unwrap(), *"a::b::c");
+    assert_eq!(s.replace::<8>("x", "yyyy").unwrap(), *"a.b.c");
+    assert_eq!(s.replace::<6>(".", "::"), Err(CapacityError::new(())));
+
+    assert_eq!(s.repeat::<10>(2).unwrap(), *"a.b.ca.b.c");
+    assert_eq!(s.repeat::<10>(0).unwrap(), *"");
+    assert!(s.repeat::<10>(usize::MAX).is_err());
+}
+
+#[test]
+fn test_arraystring_str_methods() {
+    // the allocating `str` methods are reached through `as_str()`
+    let s = ArrayString::<8>::from("a.B.c").unwrap();
+    assert_eq!(s.as_str().to_lowercase(), "a.b.c");
+    assert_eq!(s.as_str().to_uppercase(), "A.B.C");
+    assert_eq!(s.as_str().replace(".", "::"), "a::B::c");
+    assert_eq!(s.as_str().repeat(2), "a.B.ca.B.c");
+    assert_eq!((*s).to_lowercase(), "a.b.c");
+}
+
+#[test]
+fn test_arraystring_from_utf8() {
+    use arrayvec::FromUtf8Error;
+
+    let s = ArrayString::<8>::from_utf8("grüße".as_bytes()).unwrap();
+    assert_eq!(&s, "grüße");
+    assert!(matches!(ArrayString::<8>::from_utf8(b"a\xc3"), Err(FromUtf8Error::Utf8(_))));
+    assert_eq!(ArrayString::<6>::from_utf8("grüße".as_bytes()),
+               Err(FromUtf8Error::Capacity(CapacityError::new(()))));
+
+    assert_eq!(ArrayString::<16>::from_utf8_lossy(b"a\xffb\xc3"), *"a\u{FFFD}b\u{FFFD}");
+    assert_eq!(ArrayString::<16>::from_utf8_lossy(b"\xe2\x82"), *"\u{FFFD}");
+    // truncated at a char boundary
+    assert_eq!(ArrayString::<6>::from_utf8_lossy("grüße".as_bytes()), *"grüß");
+    assert_eq!(ArrayString::<5>::from_utf8_lossy("grüße".as_bytes()), *"grü");
+    assert_eq!(ArrayString::<4>::from_utf8_lossy(b"abc\xff"), *"abc");
+
+    let s = unsafe { ArrayString::<8>::from_utf8_unchecked("grüße".as_bytes()) };
+    assert_eq!(&s, "grüße");
+}
+
+#[test]
+fn test_arraystring_from_utf16() {
+    use arrayvec::FromUtf16Error;
+
+    let v: Vec<u16> = "grüße 𝄞".encode_utf16().collect();
+    assert_eq!(ArrayString::<16>::from_utf16(&v).unwrap(), *"grüße 𝄞");
+    assert_eq!(ArrayString::<10>::from_utf16(&v),
+               Err(FromUtf16Error::Capacity(CapacityError::new(()))));
+    assert_eq!(ArrayString::<16>::from_utf16(&[0x61, 0xDC00]), Err(FromUtf16Error::Utf16));
+
+    assert_eq!(ArrayString::<16>::from_utf16_lossy(&[0x61, 0xDC00, 0xD800]), *"a\u{FFFD}\u{FFFD}");
+    assert_eq!(ArrayString::<10>::from_utf16_lossy(&v), *"grüße ");
+}
+
+#[test]
+fn test_arraystring_bytes() {
+    let s = ArrayString::<8>::from("grüße").unwrap();
+    let mut bytes = s.into_bytes();
+    assert_eq!(&bytes[..], "grüße".as_bytes());
+    assert_eq!(bytes.capacity(), 8);
+
+    bytes.push(b'!');
+    let s = ArrayString::from_utf8_vec(bytes.clone()).unwrap();
+    assert_eq!(&s, "grüße!");
+
+    bytes.truncate(3);
+    assert!(ArrayString::from_utf8_vec(bytes).is_err());
+
+    let mut s = ArrayString::<8>::from("abc").unwrap();
+    unsafe {
+        let v = s.as_mut_vec();
+        v.push(b'd');
+        v.swap(0, 3);
+    }
+    assert_eq!(&s, "dbca");
+    assert_eq!(s.len(), 4);
+}
+
+#[cfg(feature="std")]
+#[test]
+fn test_arraycstring() {
+    use arrayvec::{ArrayCString, CStringError};
+    use std::convert::TryFrom;
+    use std::ffi::{CStr, CString};
+
+    let mut s = ArrayCString::<8>::new();
+    assert_eq!(s.to_bytes_with_nul(), b"\0");
+    s.try_push_bytes(b"abc").unwrap();
+    s.push(b'd');
+    assert_eq!(s.len(), 4);
+    assert_eq!(s.to_bytes_with_nul(), b"abcd\0");
+    assert_eq!(s.try_push_bytes(b"x\0"), Err(CStringError::InteriorNul(5)));
+    assert_eq!(s.try_push_bytes(b"xyzw"), Err(CStringError::Capacity(CapacityError::new(()))));
+    assert_eq!(s.to_bytes(), b"abcd");
+    s.try_push_bytes(b"xyz").unwrap();
+    assert!(s.is_full());
+    s.truncate(2);
+    assert_eq!(s.to_bytes_with_nul(), b"ab\0");
+    s.clear();
+    assert!(s.is_empty());
+
+    let c = CString::new("hello").unwrap();
+    let s = ArrayCString::<6>::try_from(c.as_c_str()).unwrap();
+    assert_eq!(s.as_c_str(), c.as_c_str());
+    assert!(ArrayCString::<5>::try_from(c.as_c_str()).is_err());
+    let ptr = s.as_ptr();
+    assert_eq!(unsafe { CStr::from_ptr(ptr) }, c.as_c_str());
+
+    let string = ArrayString::<5>::from("hello").unwrap();
+    assert!(ArrayCString::try_from(string).is_err());
+    let s = ArrayCString::<8>::try_from("hello").unwrap();
+    assert_eq!(&s.to_array_string().unwrap(), "hello");
+    assert_eq!(ArrayCString::<8>::try_from("he\0llo"), Err(CStringError::InteriorNul(2)));
+
+    let s = ArrayCString::<4>::from_bytes(b"\xff").unwrap();
+    assert!(s.to_array_string().is_err());
+}
+
+#[test]
+fn test_arrayasciistring() {
+    use arrayvec::{ArrayAsciiString, AsciiError};
+    use std::cmp::Ordering;
+    use std::collections::hash_map::DefaultHasher;
+    use std::convert::TryFrom;
+    use std::hash::Hasher;
+
+    let mut s = ArrayAsciiString::<8>::from("IBM").unwrap();
+    s.push('.');
+    s.push_str("N");
+    assert_eq!(&s, "IBM.N");
+    assert_eq!(s.try_push_str("ab€"), Err(AsciiError::NonAscii(7)));
+    assert_eq!(s.try_push_str("abcd"), Err(AsciiError::Capacity(CapacityError::new(()))));
+    assert_eq!(s.len(), 5);
+
+    assert_eq!(s.char_at(3), '.');
+    s.set_char_at(3, '-');
+    assert_eq!(s.slice(..3), "IBM");
+    assert_eq!(s.slice(3..=3), "-");
+    assert_eq!(&s[4..], "N");
+    assert_eq!(s.pop(), Some('N'));
+    s.truncate(1);
+    assert_eq!(&s, "I");
+
+    let a = ArrayAsciiString::<16>::from("Accept").unwrap();
+    assert_eq!(a.cmp_ignore_ascii_case("ACCEPT"), Ordering::Equal);
+    assert_eq!(a.cmp_ignore_ascii_case("accepts"), Ordering::Less);
+    assert_eq!(a.cmp_ignore_ascii_case("ab"), Ordering::Greater);
+    let hash = |s: &str| {
+        let mut h = DefaultHasher::new();
+        ArrayAsciiString::<16>::from(s).unwrap().hash_ignore_ascii_case(&mut h);
+        h.finish()
+    };
+    assert_eq!(hash("Accept"), hash("aCCEPT"));
+    assert_ne!(hash("Accept"), hash("Accepted"));
+
+    let string: ArrayString<16> = a.into();
+    assert_eq!(&string, "Accept");
+    assert_eq!(ArrayAsciiString::try_from(string).unwrap(), a);
+    let string = ArrayString::<16>::from("naïve").unwrap();
+    assert_eq!(ArrayAsciiString::try_from(string), Err(AsciiError::NonAscii(2)));
+}
+
+#[test]
+#[should_panic]
+fn test_arrayasciistring_set_char_at_non_ascii() {
+    let mut s = arrayvec::ArrayAsciiString::<8>::from("abc").unwrap();
+    s.set_char_at(0, 'é');
+}
+
+#[test]
+fn test_len_uint_sizes() {
+    assert_eq!(mem::size_of::<ArrayString<15, u8>>(), 16);
+    assert_eq!(mem::size_of::<ArrayString<14, u16>>(), 16);
+    assert_eq!(mem::size_of::<ArrayString<15>>(), 20);
+    assert_eq!(mem::size_of::<ArrayVec<u8, 7, u8>>(), 8);
+    assert_eq!(mem::size_of::<ArrayVec<u16, 3, u16>>(), 8);
+    assert_eq!(mem::size_of::<ArrayVec<u64, 2, usize>>(), 24);
+}
+
+#[test]
+fn test_len_uint_default_inference() {
+    // the length type is not inferred, `new` and `default` use `u32`
+    let mut v = ArrayVec::new();
+    v.push(1u8);
+    let a: [u8; 1] = v.into_inner().unwrap();
+    assert_eq!(a, [1]);
+
+    let mut s = ArrayString::new();
+    s.push_str("a");
+    let t: ArrayString<1> = s;
+    assert_eq!(&t, "a");
+
+    let v: ArrayVec<u8, 2> = Default::default();
+    assert!(v.is_empty());
+}
+
+#[test]
+fn test_len_uint_arrayvec() {
+    let mut v = ArrayVec::<i32, 255, u8>::new_with_len_type();
+    v.extend(0..255);
+    assert_eq!(v.len(), 255);
+    assert!(v.is_full());
+    assert_eq!(v.try_push(1), Err(CapacityError::new(1)));
+    v.retain(|x| *x % 2 == 0);
+    assert_eq!(v.len(), 128);
+    assert_eq!(v.drain(..100).len(), 100);
+    assert_eq!(&v[..3], &[200, 202, 204]);
+    let w: ArrayVec<i32, 255, u8> = v.iter().cloned().collect();
+    assert_eq!(v, w);
+    let mut u: ArrayVec<i32, 4, u16> = vec![1].into_iter().collect();
+    u.append(&mut ArrayVec::<i32, 3>::from([2, 3, 4]));
+    assert_eq!(&u[..], &[1, 2, 3, 4]);
+    assert_eq!(v.into_iter().count(), 28);
+}
+
+#[test]
+fn test_len_uint_arraystring() {
+    let mut s = ArrayString::<15, u8>::from("hello").unwrap();
+    s.push_str(" world");
+    assert_eq!(&s, "hello world");
+    assert_eq!(s.remaining_capacity(), 4);
+    assert!(s.try_push_str("!!!!!").is_err());
+    let bytes = s.into_bytes();
+    assert_eq!(&bytes[..], b"hello world");
+    let s = ArrayString::from_utf8_vec(bytes).unwrap();
+    assert_eq!(s.split_whitespace().collect::<ArrayString<15, u16>>(), *"helloworld");
+
+    const S: ArrayString<255, u8> = ArrayString::new_with_len_type_const();
+    const LEN: usize = S.len();
+    assert_eq!(LEN, 0);
+}
+
+#[should_panic(expected="largest supported capacity is u8::MAX")]
+#[test]
+fn deny_capacity_over_len_uint() {
+    let _v = ArrayVec::<(), 256, u8>::new_with_len_type();
+}
+
+#[should_panic(expected="largest supported capacity")]
+#[test]
+fn deny_capacity_over_len_uint_string() {
+    let _s = ArrayString::<65536, u16>::new_with_len_type();
+}
+
+#[test]
+fn test_arrayveccopy() {
+    use arrayvec::ArrayVecCopy;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    struct Point { x: i32, y: i32 }
+
+    let mut a = ArrayVecCopy::<Point, 4>::new();
+    a.push(Point { x: 1, y: 2 });
+    a.extend(vec![Point { x: 3, y: 4 }, Point { x: 5, y: 6 }]);
+    let b = a;
+    assert_eq!(a.pop(), Some(Point { x: 5, y: 6 }));
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.len(), 3);
+    assert_eq!(b[2], Point { x: 5, y: 6 });
+    a.insert(0, Point { x: 0, y: 0 });
+    a.retain(|p| p.x != 1);
+    assert_eq!(&a[..], &[Point { x: 0, y: 0 }, Point { x: 3, y: 4 }]);
+    assert_ne!(a, b);
+    assert_eq!(a.into_iter().map(|p| p.y).sum::<i32>(), 4);
+
+    let c: ArrayVecCopy<u8, 8, u8> = (0..5).collect();
+    assert_eq!(mem::size_of_val(&c), 9);
+    let mut d: ArrayVec<u8, 8, u8> = c.into();
+    d.push(5);
+    let d = ArrayVecCopy::from(d);
+    assert_eq!(&d[..], &[0, 1, 2, 3, 4, 5]);
+    assert_eq!(ArrayVecCopy::from([1, 2, 3]).take().into_inner(), Ok([1, 2, 3]));
+
+    // methods that return a vector by value return an `ArrayVecCopy`
+    let mut e: ArrayVecCopy<u8, 8, u8> = d.into_capacity();
+    let f: ArrayVecCopy<u8, 4, u8> = e.split_off(4);
+    let g = e;
+    assert_eq!(&f[..], &[4, 5]);
+    assert_eq!(g.into_inner(), Err(e));
+    let mut h = ArrayVecCopy::<u8, 2, u8>::new_with_len_type();
+    assert!(h.try_append(&mut e).is_err());
+    assert_eq!(f.try_into_capacity::<2>().map(|v| v.into_inner()), Ok(Ok([4, 5])));
+}
+
+#[test]
+fn test_arrayvec_view() {
+    use arrayvec::ArrayVecView;
+    use std::rc::Rc;
+
+    fn fill(v: &mut ArrayVecView<Rc<i32>>, x: &Rc<i32>) {
+        while v.try_push(x.clone()).is_ok() { }
+    }
+
+    let x = Rc::new(1);
+    let mut small = ArrayVec::<Rc<i32>, 2>::new();
+    let mut large = ArrayVec::<Rc<i32>, 5>::new();
+    fill(small.as_mut_view(), &x);
+    fill(large.as_mut_view(), &x);
+    assert_eq!(small.len(), 2);
+    assert_eq!(large.as_view().capacity(), 5);
+    assert!(large.as_view().is_full());
+    assert_eq!(Rc::strong_count(&x), 8);
+    drop(small);
+    assert_eq!(Rc::strong_count(&x), 6);
+
+    let view = large.as_mut_view();
+    view.truncate(3);
+    assert_eq!(view.remaining_capacity(), 2);
+    assert_eq!(Rc::strong_count(&x), 4);
+    view.clear();
+    assert!(view.is_empty());
+    assert_eq!(Rc::strong_count(&x), 1);
+
+    let mut v = ArrayVec::<i32, 8, u8>::new_with_len_type();
+    let view = v.as_mut_view();
+    view.extend(0..6);
+    view.insert(0, 10);
+    assert_eq!(view.remove(1), 0);
+    assert_eq!(view.swap_remove(0), 10);
+    assert_eq!(view.pop_at(10), None);
+    assert_eq!(view.pop(), Some(4));
+    assert_eq!(view, &[5, 1, 2, 3][..]);
+    view.retain(|x| *x != 2);
+    assert_eq!(view.drain(1..).collect::<Vec<_>>(), vec![1, 3]);
+    view.try_extend_from_slice(&[7, 8, 9]).unwrap();
+    assert!(view.try_extend_from_slice(&[0; 5]).is_err());
+    assert_eq!(&v[..], &[5, 7, 8, 9]);
+    assert_eq!(format!("{:?}", v.as_view()), "[5, 7, 8, 9]");
+}
+
+#[test]
+fn test_arraystring_view() {
+    use arrayvec::{ArrayStringView, LenUint};
+    use std::fmt::Write;
+
+    fn describe<L: LenUint>(s: &mut ArrayStringView<L>, n: i32) -> std::fmt::Result {
+        write!(s, "n = {}", n)
+    }
+
+    let mut small = ArrayString::<4>::new();
+    let mut large = ArrayString::<16, u8>::new_with_len_type();
+    assert!(describe(small.as_mut_view(), 5).is_err());
+    describe(large.as_mut_view(), 5).unwrap();
+    assert_eq!(&large[..], "n = 5");
+
+    let view = large.as_mut_view();
+    assert_eq!(view.capacity(), 16);
+    view.push('é');
+    assert_eq!(view.pop(), Some('é'));
+    assert_eq!(view.remove(1), ' ');
+    view.truncate(3);
+    assert_eq!(view, "n= ");
+    view.clear();
+    assert!(view.is_empty());
+    assert_eq!(view.to_string(), "");
+}
+
+#[test]
+fn test_slicevec() {
+    use arrayvec::SliceVec;
+    use std::mem::MaybeUninit;
+    use std::rc::Rc;
+
+    let x = Rc::new(1);
+    let mut storage: [MaybeUninit<Rc<i32>>; 4] = [(); 4].map(|_| MaybeUninit::uninit());
+    {
+        let mut v = SliceVec::new(&mut storage);
+        assert_eq!(v.capacity(), 4);
+        v.extend((0..3).map(|_| x.clone()));
+        v.insert(1, x.clone());
+        assert!(v.is_full());
+        assert!(v.try_push(x.clone()).is_err());
+        assert_eq!(Rc::strong_count(&x), 5);
+        v.truncate(2);
+        assert_eq!(Rc::strong_count(&x), 3);
+    }
+    // the remaining elements are dropped with the `SliceVec`
+    assert_eq!(Rc::strong_count(&x), 1);
+
+    let mut storage = [MaybeUninit::uninit(); 8];
+    let mut v = SliceVec::new(&mut storage[2..]);
+    v.extend(0..6);
+    assert_eq!(v.remove(0), 0);
+    assert_eq!(v.swap_remove(0), 1);
+    assert_eq!(v.pop_at(10), None);
+    assert_eq!(v.pop(), Some(4));
+    assert_eq!(&v[..], &[5, 2, 3]);
+    v.retain(|x| *x != 2);
+    assert_eq!(v.drain(1..).collect::<Vec<_>>(), vec![3]);
+    v.try_extend_from_slice(&[7, 8]).unwrap();
+    assert!(v.try_extend_from_slice(&[0; 4]).is_err());
+    assert_eq!(format!("{:?}", v), "[5, 7, 8]");
+    // the tail is moved back when the drain is dropped unconsumed
+    drop(v.drain(..1));
+    assert_eq!(&v[..], &[7, 8]);
+    v.clear();
+    assert!(v.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "SliceVec::remove: index 2 is out of bounds")]
+fn test_slicevec_remove_oob() {
+    use arrayvec::SliceVec;
+    use std::mem::MaybeUninit;
+
+    let mut storage = [MaybeUninit::uninit(); 4];
+    let mut v = SliceVec::new(&mut storage);
+    v.push(1);
+    v.remove(2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_spillvec() {
+    use arrayvec::SpillVec;
+
+    let mut v = SpillVec::<String, 3>::new();
+    assert!(v.is_inline());
+    for s in ["a", "b", "c"] {
+        v.push(s.to_string());
+    }
+    assert!(v.is_inline());
+    v.insert(0, "z".to_string());
+    assert!(!v.is_inline());
+    assert!(v.capacity() >= 4);
+    assert_eq!(&v[..], &["z", "a", "b", "c"]);
+    assert_eq!(v.remove(1), "a");
+    assert_eq!(v.swap_remove(0), "z");
+    v.retain(|s| s != "b");
+    assert_eq!(&v[..], &["c"]);
+    v.shrink_to_inline();
+    assert!(v.is_inline());
+    assert_eq!(v.pop().as_deref(), Some("c"));
+    assert!(v.is_empty());
+
+    let v: SpillVec<i32, 4> = (0..3).collect();
+    assert!(v.is_inline());
+    assert_eq!(v.clone().into_iter().rev().collect::<Vec<_>>(), vec![2, 1, 0]);
+    let mut w = v.clone();
+    w.extend(3..10);
+    assert!(!w.is_inline());
+    assert_eq!(w.len(), 10);
+    assert_ne!(v, w);
+    w.truncate(3);
+    assert_eq!(v, w);
+    assert_eq!(w.into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(Vec::from(v), vec![0, 1, 2]);
+
+    let v = SpillVec::<u8, 2>::from(ArrayVec::from([1, 2]));
+    assert!(v.is_inline());
+    assert_eq!(format!("{:?}", v), "[1, 2]");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_spillstring() {
+    use arrayvec::SpillString;
+    use std::fmt::Write;
+
+    let mut s = SpillString::<4>::from("ab");
+    assert!(s.is_inline());
+    s.push('c');
+    s.push('é');
+    assert!(!s.is_inline());
+    assert_eq!(&s[..], "abcé");
+    assert_eq!(s.pop(), Some('é'));
+    s.shrink_to_inline();
+    assert!(s.is_inline());
+    write!(s, "{}", 12345).unwrap();
+    assert!(!s.is_inline());
+    assert_eq!(s.to_string(), "abc12345");
+    s.truncate(2);
+    assert_eq!(s, *"ab");
+    assert_eq!(String::from(s.clone()), "ab");
+    s.clear();
+    assert!(s.is_empty());
+    assert_eq!(SpillString::<2>::from(ArrayString::from("hi").unwrap()).into_string(), "hi");
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_alloc_conversions() {
+    use std::borrow::Cow;
+    use std::convert::TryFrom;
+
+    let array = ArrayVec::<String, 4, u8>::try_from(vec!["a".to_string(), "b".to_string()]).unwrap();
+    assert_eq!(&array[..], &["a", "b"]);
+    let err = ArrayVec::<i32, 2>::try_from(vec![1, 2, 3]).unwrap_err();
+    assert_eq!(err.element(), vec![1, 2, 3]);
+
+    let cow = Cow::from(&array);
+    assert!(matches!(cow, Cow::Borrowed(_)));
+    assert_eq!(cow.into_owned(), vec!["a", "b"]);
+    let boxed: Box<[String]> = array.clone().into();
+    assert_eq!(&boxed[..], &["a", "b"]);
+    let cow: Cow<[String]> = array.clone().into();
+    assert!(matches!(cow, Cow::Owned(_)));
+    let vec: Vec<String> = array.into();
+    assert_eq!(vec, ["a", "b"]);
+
+    let string = ArrayString::<8, u8>::try_from(String::from("hello")).unwrap();
+    assert_eq!(ArrayString::<4>::try_from(String::from("hello")).unwrap_err().element(), "hello");
+    assert!(matches!(Cow::from(&string), Cow::Borrowed("hello")));
+    let cow: Cow<str> = string.into();
+    assert!(matches!(cow, Cow::Owned(_)));
+    let boxed: Box<str> = string.into();
+    assert_eq!(&*boxed, "hello");
+    assert_eq!(String::from(string), "hello");
+}
+
+#[test]
+fn test_try_extend() {
+    let mut v = ArrayVec::<String, 3>::new();
+    v.try_extend(vec!["a".to_string()]).unwrap();
+    let words = ["b", "c", "d", "e"].iter().map(|s| s.to_string());
+    let (overflow, rest) = v.try_extend(words).unwrap_err();
+    assert!(v.is_full());
+    assert_eq!(&v[..], &["a", "b", "c"]);
+    assert_eq!(overflow, "d");
+    assert_eq!(rest.collect::<Vec<_>>(), ["e"]);
+
+    // an iterator that exactly fills the vector is not an error
+    let mut v = ArrayVec::<i32, 2, u8>::new_with_len_type();
+    v.try_extend(0..2).unwrap();
+    assert!(v.try_extend(Vec::new()).is_ok());
+    assert_eq!(v.try_extend(Some(5)).unwrap_err().0, 5);
+
+    let mut v = ArrayVec::<(), 2>::new();
+    assert!(v.try_extend(std::iter::repeat(())).is_err());
+    assert_eq!(v.len(), 2);
+}
+
+#[test]
+fn test_try_from_iter() {
+    let v = ArrayVec::<i32, 4>::try_from_iter(0..4).unwrap();
+    assert_eq!(&v[..], &[0, 1, 2, 3]);
+
+    let (v, overflow, mut rest) = ArrayVec::<i32, 4>::try_from_iter(0..10).unwrap_err();
+    assert_eq!(&v[..], &[0, 1, 2, 3]);
+    assert_eq!(overflow, 4);
+    assert_eq!(rest.next(), Some(5));
+
+    let empty = ArrayVec::<i32, 0>::try_from_iter(None).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_array_vec_chunks() {
+    use arrayvec::ArrayVecChunksExt;
+
+    let chunks: Vec<ArrayVec<i32, 4>> = (0..10).array_vec_chunks().collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(&chunks[0][..], &[0, 1, 2, 3]);
+    assert_eq!(&chunks[2][..], &[8, 9]);
+    assert!(chunks.iter().all(|c| c.capacity() == 4));
+
+    let mut chunks = (0..8).array_vec_chunks::<4>();
+    assert_eq!(chunks.size_hint(), (2, Some(2)));
+    assert_eq!(chunks.next().map(|c| c.len()), Some(4));
+    assert_eq!(chunks.size_hint(), (1, Some(1)));
+    assert_eq!(chunks.next().map(|c| c.len()), Some(4));
+    assert!(chunks.next().is_none());
+    assert!(chunks.next().is_none());
+
+    let mut empty = std::iter::empty::<String>().array_vec_chunks::<2>();
+    assert!(empty.next().is_none());
+
+    let words = ["a", "b", "c"].iter().map(|s| s.to_string());
+    let mut chunks = words.array_vec_chunks::<2>();
+    assert_eq!(chunks.size_hint(), (2, Some(2)));
+    assert_eq!(&chunks.next().unwrap()[..], &["a", "b"]);
+    assert_eq!(chunks.into_inner().next().as_deref(), Some("c"));
+
+    let chunks = (0..).filter(|x| x % 3 == 0).array_vec_chunks::<5>();
+    assert_eq!(chunks.size_hint(), (0, None));
+    assert_eq!(&chunks.take(2).last().unwrap()[..], &[15, 18, 21, 24, 27]);
+}